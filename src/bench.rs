@@ -0,0 +1,192 @@
+use std::{
+  fs, hint,
+  time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::solvers::{DynSolver, ParseError, Part, Progress, parse, runner};
+
+pub struct BenchOptions {
+  pub warmup: u32,
+  pub samples: u32,
+  pub save: Option<String>,
+  pub compare: Option<String>,
+  /// Allowed slowdown of the median, in percent, before a phase counts as a regression
+  pub threshold: f64,
+  pub params: Vec<(String, i64)>,
+  pub input: Option<String>,
+  /// Only benchmark this part, both if None
+  pub part: Option<Part>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Baseline {
+  pub day: u8,
+  pub phases: Vec<PhaseStats>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PhaseStats {
+  pub phase: String,
+  pub samples: u32,
+  pub min_ns: u64,
+  pub median_ns: u64,
+  pub mean_ns: u64,
+  pub stddev_ns: u64,
+}
+
+impl PhaseStats {
+  fn new(phase: &str, mut samples: Vec<Duration>) -> Self {
+    samples.sort();
+    let nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+    let n = nanos.len() as f64;
+    let mid = nanos.len() / 2;
+    let median = if nanos.len().is_multiple_of(2) {
+      (nanos[mid - 1] + nanos[mid]) / 2.0
+    } else {
+      nanos[mid]
+    };
+    let mean = nanos.iter().sum::<f64>() / n;
+    let variance = if nanos.len() > 1 {
+      nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    } else {
+      0.0
+    };
+
+    Self {
+      phase: phase.to_string(),
+      samples: nanos.len() as u32,
+      min_ns: nanos[0] as u64,
+      median_ns: median as u64,
+      mean_ns: mean as u64,
+      stddev_ns: variance.sqrt() as u64,
+    }
+  }
+}
+
+/// Returns false if any phase regressed against the compared baseline.
+pub fn bench(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  options: &BenchOptions,
+) -> Result<bool, String> {
+  let input_path = runner::input_path(day_path, options.input.as_deref());
+  let input = parse::read_input(solver.day(), &input_path).map_err(|e| e.to_string())?;
+  let parsed_input = solver.parse(&input).map_err(|e| e.to_string())?;
+  let params = runner::real_params(solver, &options.params);
+
+  println!(
+    "Day {}: {} ({} warmup, {} samples)",
+    solver.day(),
+    solver.title(),
+    options.warmup,
+    options.samples
+  );
+
+  let mut phases =
+    vec![sample("parse", options, || solver.parse(&input).map(|_| ())).map_err(|e| e.to_string())?];
+  for part in Part::select(options.part) {
+    let phase = format!("part {}", part);
+    phases.push(
+      sample(&phase, options, || {
+        hint::black_box(solver.solve(part, &parsed_input, &params, &Progress::disabled()));
+        Ok(())
+      })
+      .map_err(|e| e.to_string())?,
+    );
+  }
+
+  println!(
+    "{:<8} {:>12} {:>12} {:>12} {:>12}",
+    "Phase", "Min", "Median", "Mean", "Std dev"
+  );
+  for stats in &phases {
+    println!(
+      "{:<8} {:>12} {:>12} {:>12} {:>12}",
+      stats.phase,
+      format!("{:?}", Duration::from_nanos(stats.min_ns)),
+      format!("{:?}", Duration::from_nanos(stats.median_ns)),
+      format!("{:?}", Duration::from_nanos(stats.mean_ns)),
+      format!("{:?}", Duration::from_nanos(stats.stddev_ns)),
+    );
+  }
+
+  let baseline = Baseline {
+    day: solver.day(),
+    phases,
+  };
+
+  let mut ok = true;
+  if let Some(path) = &options.compare {
+    let previous = load(path)?;
+    if previous.day != baseline.day {
+      return Err(format!(
+        "{} is a baseline for day {}, not day {}",
+        path, previous.day, baseline.day
+      ));
+    }
+    ok = compare(&previous, &baseline, options.threshold);
+  }
+
+  if let Some(path) = &options.save {
+    let json = serde_json::to_string_pretty(&baseline).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("{}: {}", path, e))?;
+    println!("Saved baseline to {}", path);
+  }
+
+  Ok(ok)
+}
+
+fn sample<F>(phase: &str, options: &BenchOptions, mut f: F) -> Result<PhaseStats, ParseError>
+where
+  F: FnMut() -> Result<(), ParseError>,
+{
+  for _ in 0..options.warmup {
+    f()?;
+  }
+
+  let mut samples = Vec::with_capacity(options.samples as usize);
+  for _ in 0..options.samples {
+    let before = Instant::now();
+    f()?;
+    samples.push(before.elapsed());
+  }
+  Ok(PhaseStats::new(phase, samples))
+}
+
+fn load(path: &str) -> Result<Baseline, String> {
+  let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+  serde_json::from_str(&json).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn compare(previous: &Baseline, current: &Baseline, threshold: f64) -> bool {
+  println!("Compared to baseline (median, threshold {}%):", threshold);
+  let mut ok = true;
+  for stats in &current.phases {
+    let Some(old) = previous.phases.iter().find(|p| p.phase == stats.phase) else {
+      println!("{:<8} not in baseline", stats.phase);
+      continue;
+    };
+
+    let change =
+      (stats.median_ns as f64 - old.median_ns as f64) / old.median_ns.max(1) as f64 * 100.0;
+    let verdict = if change > threshold {
+      ok = false;
+      "REGRESSION"
+    } else if change < -threshold {
+      "improved"
+    } else {
+      "unchanged"
+    };
+    println!(
+      "{:<8} {:>12} -> {:>12} {:>+8.1}% {}",
+      stats.phase,
+      format!("{:?}", Duration::from_nanos(old.median_ns)),
+      format!("{:?}", Duration::from_nanos(stats.median_ns)),
+      change,
+      verdict
+    );
+  }
+  ok
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use advent_of_code_2025::solvers::{ExampleMode, Part, RunOptions};
+
+#[derive(Parser)]
+#[command(
+  about = "Advent of Code 2025 solutions",
+  after_help = "Exit codes: 0 on success, 1 if an example or benchmark check failed or a part timed out, 2 on errors."
+)]
+pub struct Cli {
+  #[command(subcommand)]
+  pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+  /// Check the examples, then solve the real input of a day
+  Run {
+    /// Day number, see `list`
+    day: u8,
+    #[command(flatten)]
+    run: RunArgs,
+    /// Solve each part this many times on one parse and report the average
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: u32,
+    /// Real input to solve instead of input/dayN/input.txt, `-` reads stdin
+    #[arg(long, value_name = "PATH")]
+    input: Option<String>,
+    /// Record the answers as accepted in input/dayN/answers.json
+    #[arg(long, conflicts_with = "input")]
+    record: bool,
+    /// Only this part, 1 or 2
+    #[arg(long, value_parser = parse_part)]
+    part: Option<Part>,
+  },
+  /// Only check the examples of a day
+  Test {
+    /// Day number, see `list`
+    day: u8,
+    /// Only this part, 1 or 2
+    #[arg(long, value_parser = parse_part)]
+    part: Option<Part>,
+  },
+  /// Benchmark parsing and both parts of a day
+  Bench {
+    /// Day number, see `list`
+    day: u8,
+    #[arg(long, default_value_t = 5)]
+    warmup: u32,
+    #[arg(long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(1..))]
+    samples: u32,
+    /// Save the results as a JSON baseline
+    #[arg(long, value_name = "PATH")]
+    save: Option<String>,
+    /// Compare against a JSON baseline saved earlier
+    #[arg(long, value_name = "PATH")]
+    compare: Option<String>,
+    /// Median slowdown in percent that counts as a regression
+    #[arg(long, default_value_t = 5.0)]
+    threshold: f64,
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, i64)>,
+    /// Real input to benchmark instead of input/dayN/input.txt, `-` reads stdin
+    #[arg(long, value_name = "PATH")]
+    input: Option<String>,
+    /// Only this part, 1 or 2
+    #[arg(long, value_parser = parse_part)]
+    part: Option<Part>,
+  },
+  /// Re-run a day whenever a file in input/dayN/ changes
+  Watch {
+    /// Day number, see `list`
+    day: u8,
+    /// How often to check for changes, in milliseconds
+    #[arg(long, default_value_t = 300)]
+    interval: u64,
+    /// Give up on a part after this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, i64)>,
+    /// Only this part, 1 or 2
+    #[arg(long, value_parser = parse_part)]
+    part: Option<Part>,
+  },
+  /// Note an answer the puzzle rejected, later runs warn when they repeat it
+  Reject {
+    /// Day number, see `list`
+    day: u8,
+    /// 1 or 2
+    #[arg(value_parser = parse_part)]
+    part: Part,
+    answer: String,
+    #[arg(long, conflicts_with = "too_low")]
+    too_high: bool,
+    #[arg(long)]
+    too_low: bool,
+  },
+  /// List the implemented days and their parameters
+  List,
+  /// Generate a solver skeleton and empty input files for a new day
+  New {
+    day: u8,
+    /// Puzzle title shown in the output
+    #[arg(long, default_value = "TODO")]
+    title: String,
+  },
+  /// Run every day and print a summary table
+  All {
+    #[command(flatten)]
+    run: RunArgs,
+  },
+}
+
+#[derive(Args)]
+pub struct RunArgs {
+  #[arg(long, value_enum, default_value_t = Format::Text)]
+  pub format: Format,
+  /// Override a puzzle parameter for the real input
+  #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+  pub params: Vec<(String, i64)>,
+  /// Solve the real input even if an example fails
+  #[arg(long = "continue", conflicts_with = "skip_examples")]
+  pub continue_on_failure: bool,
+  /// Do not check the examples at all
+  #[arg(long)]
+  pub skip_examples: bool,
+  /// Give up on a part after this many seconds and move on
+  #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+  pub timeout: Option<Duration>,
+}
+
+impl RunArgs {
+  pub fn options(&self, repeat: u32, input: Option<String>) -> RunOptions {
+    RunOptions {
+      repeat,
+      input,
+      record: false,
+      part: None,
+      timeout: self.timeout,
+      examples: if self.skip_examples {
+        ExampleMode::Skip
+      } else if self.continue_on_failure {
+        ExampleMode::Continue
+      } else {
+        ExampleMode::Check
+      },
+      params: self.params.clone(),
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+  Text,
+  Json,
+}
+
+fn parse_part(part: &str) -> Result<Part, String> {
+  part
+    .parse()
+    .ok()
+    .and_then(Part::from_number)
+    .ok_or_else(|| format!("expected 1 or 2, got {}", part))
+}
+
+fn parse_timeout(seconds: &str) -> Result<Duration, String> {
+  seconds
+    .parse()
+    .ok()
+    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+    .ok_or_else(|| format!("expected a number of seconds, got {}", seconds))
+}
+
+fn parse_param(param: &str) -> Result<(String, i64), String> {
+  let (name, value) = param
+    .split_once('=')
+    .ok_or_else(|| format!("expected NAME=VALUE, got {}", param))?;
+  let value = value
+    .parse()
+    .map_err(|_| format!("expected an integer value for {}", name))?;
+  Ok((name.to_string(), value))
+}
@@ -0,0 +1,77 @@
+use serde::Serialize;
+
+use crate::solvers::{DayReport, ParseError, alloc::AllocStats};
+
+#[derive(Serialize)]
+struct DayJson<'a> {
+  day: u8,
+  title: &'a str,
+  input: &'a str,
+  parse_ns: u64,
+  /// Only with the count-alloc feature
+  #[serde(skip_serializing_if = "Option::is_none")]
+  parse_alloc: Option<AllocStats>,
+  parts: Vec<PartJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct PartJson<'a> {
+  part: u8,
+  /// Null if the part timed out
+  answer: Option<&'a str>,
+  solve_ns: u64,
+  /// Average of the runs after the first, only with --repeat
+  #[serde(skip_serializing_if = "Option::is_none")]
+  repeat_avg_ns: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  alloc: Option<AllocStats>,
+  example: &'static str,
+  /// Comparison against the answer ledger, omitted if it knows nothing
+  #[serde(skip_serializing_if = "Option::is_none")]
+  ledger: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorJson {
+  day: u8,
+  error: String,
+}
+
+/// Prints the report as a single line of JSON.
+pub fn print_report(report: &DayReport) {
+  let json = DayJson {
+    day: report.day,
+    title: report.title,
+    input: &report.input_path,
+    parse_ns: report.parse_duration.as_nanos() as u64,
+    parse_alloc: report.parse_alloc,
+    parts: report
+      .parts
+      .iter()
+      .map(|part| PartJson {
+        part: part.part.number(),
+        answer: part.answer.as_deref(),
+        solve_ns: part.duration.as_nanos() as u64,
+        repeat_avg_ns: part.repeat_average.map(|d| d.as_nanos() as u64),
+        alloc: part.alloc,
+        example: if part.examples.is_empty() {
+          "skipped"
+        } else if part.examples.iter().all(|e| e.passed()) {
+          "passed"
+        } else {
+          "failed"
+        },
+        ledger: part.verdict.as_ref().map(|v| v.to_string()),
+      })
+      .collect(),
+  };
+  println!("{}", serde_json::to_string(&json).unwrap());
+}
+
+pub fn print_error(day: u8, error: &ParseError) {
+  let json = ErrorJson {
+    day,
+    error: error.to_string(),
+  };
+  println!("{}", serde_json::to_string(&json).unwrap());
+}
@@ -0,0 +1,10 @@
+//! Advent of Code 2025 solutions as a library: the `Solver` trait and registry of days,
+//! the runner behind the binary's commands, and utilities shared between days.
+
+pub mod bench;
+pub mod json;
+pub mod scaffold;
+pub mod solvers;
+pub mod summary;
+pub mod util;
+pub mod watch;
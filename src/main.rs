@@ -1,33 +1,289 @@
-use solvers::Solver;
-use std::env;
+use std::{process::ExitCode, time::Duration};
 
-mod solvers;
+use advent_of_code_2025::{
+  bench, json, scaffold,
+  solvers::{self, DynSolver, ExampleMode, Hint, Ledger, Part, RunOptions, runner},
+  summary, watch,
+};
+use clap::Parser;
+use cli::{Cli, Command, Format, RunArgs};
 
-fn main() {
-  let args: Vec<String> = env::args().collect();
-  let day = match args.get(1) {
-    Some(day) => day,
-    None => {
-      panic!("Day to run is required");
+mod cli;
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+  let result = match cli.command {
+    Command::Run {
+      day,
+      run: args,
+      repeat,
+      input,
+      record,
+      part,
+    } => run(
+      day,
+      &args,
+      RunOptions {
+        record,
+        part,
+        ..args.options(repeat, input)
+      },
+    ),
+    Command::Test { day, part } => test(day, part),
+    Command::Bench {
+      day,
+      warmup,
+      samples,
+      save,
+      compare,
+      threshold,
+      params,
+      input,
+      part,
+    } => run_bench(
+      day,
+      bench::BenchOptions {
+        warmup,
+        samples,
+        save,
+        compare,
+        threshold,
+        params,
+        input,
+        part,
+      },
+    ),
+    Command::Watch {
+      day,
+      interval,
+      timeout,
+      params,
+      part,
+    } => run_watch(
+      day,
+      RunOptions {
+        examples: ExampleMode::Continue,
+        params,
+        part,
+        timeout,
+        ..RunOptions::default()
+      },
+      Duration::from_millis(interval),
+    ),
+    Command::Reject {
+      day,
+      part,
+      answer,
+      too_high,
+      too_low,
+    } => {
+      let hint = match (too_high, too_low) {
+        (true, _) => Some(Hint::TooHigh),
+        (_, true) => Some(Hint::TooLow),
+        _ => None,
+      };
+      reject(day, part, &answer, hint).map(|_| true)
+    }
+    Command::List => {
+      list();
+      Ok(true)
     }
+    Command::New { day, title } => scaffold::scaffold(day, &title).map(|_| true),
+    Command::All { run: args } => run_all(&args),
   };
 
-  run(day);
+  match result {
+    Ok(true) => ExitCode::SUCCESS,
+    Ok(false) => ExitCode::from(1),
+    Err(error) => {
+      eprintln!("Error: {}", error);
+      ExitCode::from(2)
+    }
+  }
+}
+
+fn find_solver(day: u8) -> Result<Box<dyn DynSolver>, String> {
+  solvers::find(day).ok_or_else(|| format!("Day {} is not implemented", day))
+}
+
+fn day_path(solver: &dyn DynSolver) -> String {
+  format!("input/day{}", solver.day())
+}
+
+/// Parameters apply to every day that declares them, but must exist on at least one
+/// and be in range for all of them.
+fn check_params(solvers: &[Box<dyn DynSolver>], params: &[(String, i64)]) -> Result<(), String> {
+  for (name, value) in params {
+    let mut declared = solvers
+      .iter()
+      .flat_map(|solver| solver.params())
+      .filter(|p| p.name == name)
+      .peekable();
+    if declared.peek().is_none() {
+      return Err(format!("Unknown parameter {}", name));
+    }
+    for param in declared {
+      param.check(*value)?;
+    }
+  }
+  Ok(())
+}
+
+/// The ledger only applies to the day's own input.txt.
+fn load_ledger(path: &str, options: &RunOptions) -> Result<Option<Ledger>, String> {
+  match options.input {
+    Some(_) => Ok(None),
+    None => Ledger::load(&Ledger::path(path)).map(Some),
+  }
 }
 
-fn run(day: &str) {
-  let path = format!("input/day{}", day);
-  match day {
-    "1" => solvers::day1::Day1::run(&path),
-    "2" => solvers::day2::Day2::run(&path),
-    "3" => solvers::day3::Day3::run(&path),
-    "4" => solvers::day4::Day4::run(&path),
-    "5" => solvers::day5::Day5::run(&path),
-    "6" => solvers::day6::Day6::run(&path),
-    "7" => solvers::day7::Day7::run(&path),
-    "8" => solvers::day8::Day8::run(&path),
-    "9" => solvers::day9::Day9::run(&path),
-    "10" => solvers::day10::Day10::run(&path),
-    _ => panic!("Day not valid or not implemented"),
+fn run(day: u8, args: &RunArgs, options: RunOptions) -> Result<bool, String> {
+  let solver = find_solver(day)?;
+  check_params(std::slice::from_ref(&solver), &args.params)?;
+  let path = day_path(solver.as_ref());
+  let mut ledger = load_ledger(&path, &options)?;
+
+  let passed = if args.format == Format::Json {
+    match runner::measure(solver.as_ref(), &path, &options, ledger.as_mut()) {
+      Ok(report) => {
+        json::print_report(&report);
+        report.passed()
+      }
+      Err(error) => {
+        json::print_error(solver.day(), &error);
+        return Err(error.to_string());
+      }
+    }
+  } else {
+    runner::run(solver.as_ref(), &path, &options, ledger.as_mut()).map_err(|e| e.to_string())?
+  };
+
+  if options.record && !passed {
+    eprintln!("Not recording the answers, an example failed or a part timed out");
+  } else if let Some(ledger) = ledger.filter(|_| options.record) {
+    let ledger_path = Ledger::path(&path);
+    ledger.save(&ledger_path)?;
+    if args.format == Format::Text {
+      println!("Recorded the answers in {}", ledger_path);
+    }
   }
+  Ok(passed)
+}
+
+fn reject(day: u8, part: Part, answer: &str, hint: Option<Hint>) -> Result<(), String> {
+  let solver = find_solver(day)?;
+  let ledger_path = Ledger::path(&day_path(solver.as_ref()));
+  let mut ledger = Ledger::load(&ledger_path)?;
+  if !ledger.reject(part, answer, hint) {
+    return Err(format!(
+      "{} is the recorded answer for day {} part {}",
+      answer, day, part
+    ));
+  }
+  ledger.save(&ledger_path)?;
+  match hint {
+    Some(hint) => println!(
+      "Rejected {} for day {} part {} as {}",
+      answer, day, part, hint
+    ),
+    None => println!("Rejected {} for day {} part {}", answer, day, part),
+  }
+  Ok(())
+}
+
+fn test(day: u8, part: Option<Part>) -> Result<bool, String> {
+  let solver = find_solver(day)?;
+  runner::test(solver.as_ref(), &day_path(solver.as_ref()), part).map_err(|e| e.to_string())
+}
+
+fn list() {
+  for solver in solvers::registry() {
+    let params: Vec<String> = solver
+      .params()
+      .iter()
+      .map(|p| {
+        let range = if (p.min, p.max) == (i64::MIN, i64::MAX) {
+          String::new()
+        } else {
+          format!(", {}..={}", p.min, p.max)
+        };
+        format!(
+          "{} (example {}, real {}{})",
+          p.name, p.example, p.real, range
+        )
+      })
+      .collect();
+    if params.is_empty() {
+      println!("{:>2}  {}", solver.day(), solver.title());
+    } else {
+      println!(
+        "{:>2}  {}  [{}]",
+        solver.day(),
+        solver.title(),
+        params.join(", ")
+      );
+    }
+  }
+}
+
+fn run_all(args: &RunArgs) -> Result<bool, String> {
+  let solvers = solvers::registry();
+  check_params(&solvers, &args.params)?;
+  let options = args.options(1, None);
+  let json = args.format == Format::Json;
+
+  let mut reports = Vec::new();
+  let mut failed = 0;
+  for solver in &solvers {
+    let path = day_path(solver.as_ref());
+    let mut ledger = load_ledger(&path, &options).unwrap_or_else(|error| {
+      eprintln!("{}", error);
+      None
+    });
+    match runner::measure(solver.as_ref(), &path, &options, ledger.as_mut()) {
+      Ok(report) => {
+        if json {
+          json::print_report(&report);
+        }
+        reports.push(report);
+      }
+      Err(error) => {
+        if json {
+          json::print_error(solver.day(), &error);
+        } else {
+          eprintln!("{}", error);
+        }
+        failed += 1;
+      }
+    }
+  }
+
+  if !json {
+    summary::print_summary(&reports);
+  }
+  if failed > 0 {
+    return Err(format!(
+      "{} of {} days could not be run",
+      failed,
+      solvers.len()
+    ));
+  }
+  Ok(reports.iter().all(|r| r.passed()))
+}
+
+fn run_watch(day: u8, options: RunOptions, interval: Duration) -> Result<bool, String> {
+  let solver = find_solver(day)?;
+  check_params(std::slice::from_ref(&solver), &options.params)?;
+  watch::watch(
+    solver.as_ref(),
+    &day_path(solver.as_ref()),
+    &options,
+    interval,
+  )
+}
+
+fn run_bench(day: u8, options: bench::BenchOptions) -> Result<bool, String> {
+  let solver = find_solver(day)?;
+  check_params(std::slice::from_ref(&solver), &options.params)?;
+  bench::bench(solver.as_ref(), &day_path(solver.as_ref()), &options)
 }
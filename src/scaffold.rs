@@ -0,0 +1,164 @@
+use std::{
+  fs,
+  io::{self, Write},
+  path::Path,
+};
+
+use crate::solvers;
+
+const SOLVERS_DIR: &str = "src/solvers";
+const INPUT_FILES: [&str; 3] = ["input.txt", "part_1_test.txt", "part_2_test.txt"];
+
+/// Generates `src/solvers/dayN.rs`, adds it to the `solvers!` list and creates empty
+/// input files. Existing files are never overwritten.
+pub fn scaffold(day: u8, title: &str) -> Result<(), String> {
+  if !(1..=12).contains(&day) {
+    return Err(format!("Day {} is not a puzzle day, expected 1 to 12", day));
+  }
+  if solvers::find(day).is_some() {
+    return Err(format!("Day {} is already registered", day));
+  }
+
+  let mod_path = format!("{}/mod.rs", SOLVERS_DIR);
+  let solver_path = format!("{}/day{}.rs", SOLVERS_DIR, day);
+  let mod_rs = fs::read_to_string(&mod_path).map_err(|e| {
+    format!(
+      "Could not read {}, run this from the repository root: {}",
+      mod_path, e
+    )
+  })?;
+  if Path::new(&solver_path).exists() {
+    return Err(format!("{} already exists", solver_path));
+  }
+  let mod_rs = register(&mod_rs, day)?;
+
+  create_new(&solver_path, &template(day, title))?;
+  fs::write(&mod_path, mod_rs).map_err(|e| format!("Could not write {}: {}", mod_path, e))?;
+  println!("Created {}", solver_path);
+  println!("Registered day{}::Day{} in {}", day, day, mod_path);
+
+  let day_path = format!("input/day{}", day);
+  fs::create_dir_all(&day_path).map_err(|e| format!("Could not create {}: {}", day_path, e))?;
+  for name in INPUT_FILES {
+    let path = format!("{}/{}", day_path, name);
+    if Path::new(&path).exists() {
+      println!("Kept existing {}", path);
+    } else {
+      create_new(&path, "")?;
+      println!("Created {}", path);
+    }
+  }
+  Ok(())
+}
+
+fn create_new(path: &str, contents: &str) -> Result<(), String> {
+  fs::OpenOptions::new()
+    .write(true)
+    .create_new(true)
+    .open(path)
+    .and_then(|mut file| file.write_all(contents.as_bytes()))
+    .map_err(|e| match e.kind() {
+      io::ErrorKind::AlreadyExists => format!("{} already exists", path),
+      _ => format!("Could not create {}: {}", path, e),
+    })
+}
+
+/// Inserts `dayN::DayN` into the `solvers!` list, keeping it sorted by day.
+fn register(mod_rs: &str, day: u8) -> Result<String, String> {
+  let start = mod_rs
+    .find("\nsolvers! {\n")
+    .ok_or("Could not find the solvers! list")?
+    + 1;
+  let end = start
+    + mod_rs[start..]
+      .find("\n}\n")
+      .ok_or("Could not find the end of the solvers! list")?;
+
+  let mut lines: Vec<String> = mod_rs[start..end].lines().map(str::to_string).collect();
+  let entry_day = |line: &str| -> Option<u8> {
+    line
+      .trim()
+      .strip_prefix("day")?
+      .split("::")
+      .next()?
+      .parse()
+      .ok()
+  };
+  let position = lines
+    .iter()
+    .position(|line| entry_day(line).is_some_and(|d| d > day))
+    .unwrap_or(lines.len());
+  lines.insert(position, format!("  day{}::Day{},", day, day));
+
+  Ok(format!(
+    "{}{}{}",
+    &mod_rs[..start],
+    lines.join("\n"),
+    &mod_rs[end..]
+  ))
+}
+
+fn template(day: u8, title: &str) -> String {
+  format!(
+    r#"use super::{{ParseError, Solver}};
+
+pub struct Day{day};
+
+impl Solver for Day{day} {{
+  const DAY: u8 = {day};
+  const TITLE: &'static str = {title:?};
+
+  type ParsedInput = Vec<String>;
+
+  type Output1 = u64;
+
+  type Output2 = u64;
+
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {{
+    Ok(input.lines().map(str::to_string).collect())
+  }}
+
+  fn part_1_test_output() -> Option<Self::Output1> {{
+    None
+  }}
+
+  fn part_1(_parsed_input: &Self::ParsedInput) -> Self::Output1 {{
+    todo!()
+  }}
+
+  fn part_2_test_output() -> Option<Self::Output2> {{
+    None
+  }}
+
+  fn part_2(_parsed_input: &Self::ParsedInput) -> Self::Output2 {{
+    todo!()
+  }}
+}}
+"#
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::register;
+
+  const MOD_RS: &str =
+    "mod tests;\n\nsolvers! {\n  day1::Day1,\n  day3::Day3,\n}\n\npub fn find() {}\n";
+
+  #[test]
+  fn registers_in_day_order() {
+    assert_eq!(
+      register(MOD_RS, 2).unwrap(),
+      "mod tests;\n\nsolvers! {\n  day1::Day1,\n  day2::Day2,\n  day3::Day3,\n}\n\npub fn find() {}\n"
+    );
+    assert_eq!(
+      register(MOD_RS, 12).unwrap(),
+      "mod tests;\n\nsolvers! {\n  day1::Day1,\n  day3::Day3,\n  day12::Day12,\n}\n\npub fn find() {}\n"
+    );
+  }
+
+  #[test]
+  fn rejects_missing_list() {
+    assert!(register("pub fn find() {}\n", 2).is_err());
+  }
+}
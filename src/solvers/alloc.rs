@@ -0,0 +1,140 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Heap usage of one phase, only available with the `count-alloc` feature.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct AllocStats {
+  pub bytes: u64,
+  pub count: u64,
+  /// Highest live memory above what was live when the phase started
+  pub peak: u64,
+}
+
+impl fmt::Display for AllocStats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "allocated {} in {} allocations, peak {}",
+      format_bytes(self.bytes),
+      self.count,
+      format_bytes(self.peak)
+    )
+  }
+}
+
+impl AllocStats {
+  /// Just the peak, for tables with little room.
+  pub fn format_peak(&self) -> String {
+    format_bytes(self.peak)
+  }
+}
+
+fn format_bytes(bytes: u64) -> String {
+  const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+  let mut value = bytes as f64;
+  let mut unit = 0;
+  while value >= 1024.0 && unit < UNITS.len() - 1 {
+    value /= 1024.0;
+    unit += 1;
+  }
+  if unit == 0 {
+    format!("{} B", bytes)
+  } else {
+    format!("{:.1} {}", value, UNITS[unit])
+  }
+}
+
+/// Counts the allocations made while `f` runs, on any thread. Always None unless built
+/// with `--features count-alloc`, which swaps in the counting global allocator.
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+  #[cfg(feature = "count-alloc")]
+  {
+    let (value, stats) = counting::track(f);
+    (value, Some(stats))
+  }
+  #[cfg(not(feature = "count-alloc"))]
+  {
+    (f(), None)
+  }
+}
+
+#[cfg(feature = "count-alloc")]
+mod counting {
+  use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+  };
+
+  use super::AllocStats;
+
+  static BYTES: AtomicU64 = AtomicU64::new(0);
+  static COUNT: AtomicU64 = AtomicU64::new(0);
+  static LIVE: AtomicU64 = AtomicU64::new(0);
+  static PEAK: AtomicU64 = AtomicU64::new(0);
+
+  struct Counting;
+
+  fn allocated(size: usize) {
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+  }
+
+  fn freed(size: usize) {
+    LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+  }
+
+  // SAFETY: all allocation is delegated to the system allocator, only counters are added
+  unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      let ptr = unsafe { System.alloc(layout) };
+      if !ptr.is_null() {
+        allocated(layout.size());
+      }
+      ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+      let ptr = unsafe { System.alloc_zeroed(layout) };
+      if !ptr.is_null() {
+        allocated(layout.size());
+      }
+      ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      unsafe { System.dealloc(ptr, layout) };
+      freed(layout.size());
+    }
+
+    /// Counted as a new allocation of the new size that frees the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+      let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+      if !new_ptr.is_null() {
+        freed(layout.size());
+        allocated(new_size);
+      }
+      new_ptr
+    }
+  }
+
+  #[global_allocator]
+  static GLOBAL: Counting = Counting;
+
+  pub fn track<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let count = COUNT.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let value = f();
+    let stats = AllocStats {
+      bytes: BYTES.load(Ordering::Relaxed) - bytes,
+      count: COUNT.load(Ordering::Relaxed) - count,
+      peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (value, stats)
+  }
+}
@@ -1,9 +1,4 @@
-use std::{
-  fs::File,
-  io::{self, BufRead},
-};
-
-use super::Solver;
+use super::{Example, Param, Params, ParseError, Progress, Solver};
 
 #[derive(PartialEq)]
 pub enum Direction {
@@ -13,26 +8,45 @@ pub enum Direction {
 
 pub struct Day1;
 
+const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
+
 impl Solver for Day1 {
+  const DAY: u8 = 1;
+  const TITLE: &'static str = "Secret Entrance";
+  const PARAMS: &'static [Param] = &[Param::new("start", 50, 50).range(0, 99)];
+
   type ParsedInput = Vec<(Direction, u32)>;
 
   type Output1 = u32;
 
   type Output2 = u32;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    io::BufReader::new(input)
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    input
       .lines()
-      .flatten()
-      .map(|line| {
-        let mut chars = line.chars();
-        let direction = match chars.next().expect("Direction") {
-          'L' => Direction::Left,
-          'R' => Direction::Right,
-          _ => panic!("Unexpected direction"),
+      .enumerate()
+      .map(|(i, text)| {
+        let line = Self::line(i, text);
+        let mut chars = text.chars();
+        let direction = match chars.next() {
+          Some('L') => Direction::Left,
+          Some('R') => Direction::Right,
+          Some(c) => return Err(line.error(&text[..c.len_utf8()], "expected direction L or R")),
+          None => return Err(line.missing("expected direction L or R")),
         };
-        let value = chars.as_str().parse::<u32>().expect("Number");
-        (direction, value)
+        let value = line.parse::<u32>(chars.as_str(), "expected rotation distance")?;
+        Ok((direction, value))
       })
       .collect()
   }
@@ -41,10 +55,26 @@ impl Solver for Day1 {
     Some(3)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 3)]
+  }
+
   fn part_1(parsed_input: &Self::ParsedInput) -> Self::Output1 {
+    Self::part_1_with(
+      parsed_input,
+      &Params::real(Self::PARAMS),
+      &Progress::disabled(),
+    )
+  }
+
+  fn part_1_with(
+    parsed_input: &Self::ParsedInput,
+    params: &Params,
+    _progress: &Progress,
+  ) -> Self::Output1 {
     parsed_input
       .iter()
-      .fold((50, 0), |(dial, count0), (dir, val)| {
+      .fold((params.get::<u32>("start"), 0), |(dial, count0), (dir, val)| {
         let mut dial = dial;
         dial = match dir {
           Direction::Left => (dial + 100 - val % 100) % 100,
@@ -63,9 +93,25 @@ impl Solver for Day1 {
     Some(6)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 6)]
+  }
+
   fn part_2(parsed_input: &Self::ParsedInput) -> Self::Output2 {
+    Self::part_2_with(
+      parsed_input,
+      &Params::real(Self::PARAMS),
+      &Progress::disabled(),
+    )
+  }
+
+  fn part_2_with(
+    parsed_input: &Self::ParsedInput,
+    params: &Params,
+    _progress: &Progress,
+  ) -> Self::Output2 {
     let mut count: u32 = 0;
-    let mut dial: u32 = 50;
+    let mut dial: u32 = params.get("start");
     for (dir, val) in parsed_input {
       for _ in 0..*val {
         dial = match dir {
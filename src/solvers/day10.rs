@@ -1,64 +1,107 @@
-use std::{
-  collections::{BinaryHeap, HashSet, VecDeque},
-  fs::File,
-  io::{self, BufRead},
-};
-
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use regex::Regex;
-use rustc_hash::FxHashMap;
 
-use super::Solver;
+use super::{Example, Params, ParseError, Progress, Solver};
+use crate::util::{
+  SearchSpace,
+  search::{astar_cost, bfs_cost},
+};
 
 type Lights = u16;
 type Buttons = Vec<Vec<u8>>;
 type Joltages = u128;
 type Machine = (Lights, Buttons, Joltages);
 
+const MAX_COUNTERS: usize = 10;
+
 pub struct Day10;
 
+const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
 impl Solver for Day10 {
+  const DAY: u8 = 10;
+  const TITLE: &'static str = "Factory";
+
   type ParsedInput = Vec<Machine>;
 
   type Output1 = u16;
 
   type Output2 = u32;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    let button_regex = Regex::new(r"\(([\d,]+)\)").unwrap();
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
     let mut machines = Vec::new();
 
-    for line in io::BufReader::new(input).lines().flatten() {
-      let (lights_str, buttons_str) = line.split_once(" ").unwrap();
-      let lights: Lights =
-        lights_str[1..lights_str.len() - 1]
-          .char_indices()
-          .fold(
-            0,
-            |mask, (i, c)| if c == '#' { mask | (1 << i) } else { mask },
-          );
-      let buttons: Buttons = button_regex
-        .captures_iter(buttons_str)
-        .map(|c| c[1].split(',').map(|n| n.parse::<u8>().unwrap()).collect())
-        .collect();
-
-      let joltages_str = buttons_str.split(" {").nth(1).unwrap();
-      let joltages: Joltages = joltages_str[..joltages_str.len() - 1]
-        .split(',')
-        .enumerate()
-        .fold(0u128, |acc, (i, n)| {
-          acc | ((n.parse::<u16>().unwrap() as u128) << (i * 9))
-        });
+    for (i, text) in input.lines().enumerate() {
+      let line = Self::line(i, text);
+      let (lights_str, buttons_str) = text
+        .split_once(" ")
+        .ok_or_else(|| line.missing("expected buttons after the indicator lights"))?;
+      let lights_inner = lights_str
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| line.error(lights_str, "expected indicator lights in brackets"))?;
+      if lights_inner.len() > Lights::BITS as usize {
+        return Err(line.error(lights_inner, "too many indicator lights"));
+      }
+      let mut lights: Lights = 0;
+      for (i, c) in lights_inner.char_indices() {
+        match c {
+          '#' => lights |= 1 << i,
+          '.' => {}
+          _ => return Err(line.error(&lights_inner[i..], "expected '#' or '.'")),
+        }
+      }
+
+      let (buttons_text, joltages_text) = buttons_str
+        .split_once('{')
+        .ok_or_else(|| line.missing("expected joltages in braces"))?;
+      let buttons: Buttons = buttons_text
+        .split_whitespace()
+        .map(|button| {
+          let toggles = button
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| line.error(button, "expected a button in parentheses"))?;
+          toggles
+            .split(',')
+            .map(|n| match n.parse::<u8>() {
+              Ok(n) if (n as usize) < MAX_COUNTERS => Ok(n),
+              _ => Err(line.error(n, "expected counter index below 10")),
+            })
+            .collect()
+        })
+        .collect::<Result<_, _>>()?;
+      if buttons.is_empty() {
+        return Err(line.error(buttons_text, "expected at least one button"));
+      }
+
+      let joltages_str = joltages_text
+        .strip_suffix('}')
+        .ok_or_else(|| line.error(joltages_text, "expected joltages in braces"))?;
+      let mut joltages: Joltages = 0;
+      for (i, n) in joltages_str.split(',').enumerate() {
+        match n.parse::<u16>() {
+          Ok(n) if i < MAX_COUNTERS && n <= 0x1FF => joltages |= (n as u128) << (i * 9),
+          _ => return Err(line.error(n, "expected at most 10 joltages below 512")),
+        }
+      }
       machines.push((lights, buttons, joltages));
     }
 
-    machines
+    Ok(machines)
   }
 
   fn part_1_test_output() -> Option<Self::Output1> {
     Some(7)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 7)]
+  }
+
   fn part_1(machines: &Self::ParsedInput) -> Self::Output1 {
     // Parallel for fun (~500us instead of ~1.5ms in release)
     machines.par_iter().map(Self::bfs_lights).sum()
@@ -68,92 +111,65 @@ impl Solver for Day10 {
     Some(33)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 33)]
+  }
+
   fn part_2(machines: &Self::ParsedInput) -> Self::Output2 {
+    Self::part_2_with(machines, &Params::default(), &Progress::disabled())
+  }
+
+  fn part_2_with(
+    machines: &Self::ParsedInput,
+    _params: &Params,
+    progress: &Progress,
+  ) -> Self::Output2 {
     let mut machines = machines.clone();
     machines.sort_by_key(|m| m.1.len());
+    progress.set_label("machines");
     machines
       .iter()
       .enumerate()
       .map(|(i, machine)| {
+        progress.set(i, machines.len());
         let max_button_size = machine.1.iter().map(|b| b.len()).max().unwrap();
-        let cost = Self::astar_joltages(machine, max_button_size) as u32;
-        println!("Machine {}", i);
-        cost
+        Self::astar_joltages(machine, max_button_size, progress)
       })
+      // Stops at the first machine left unsolved after a timeout, the sum is discarded
+      .map_while(|presses| presses.map(u32::from))
       .sum()
   }
 }
 
 impl Day10 {
-  fn bfs_lights((goal_lights, buttons, _): &Machine) -> u16 {
-    let mut frontier = VecDeque::from([(0u16, 0u16)]);
-    let mut reached = HashSet::from([0u16]);
-    while let Some((lights, depth)) = frontier.pop_front() {
-      if lights == *goal_lights {
-        return depth;
-      }
-      for button in buttons {
-        let mut next_lights = lights;
-        for &toggle in button {
-          next_lights ^= 1 << toggle;
-        }
-        if !reached.contains(&next_lights) {
-          reached.insert(next_lights);
-          frontier.push_back((next_lights, depth + 1));
-        }
-      }
-    }
-
-    panic!("No path found");
+  fn bfs_lights((goal, buttons, _): &Machine) -> u16 {
+    bfs_cost(&LightsSpace { goal: *goal, buttons }, 0).expect("No path found")
   }
 
-  fn astar_joltages((_, buttons, goal_joltages): &Machine, max_button_size: usize) -> u16 {
-    let init_node = AStarNode {
-      joltages: 0,
-      cost: 0,
-      heuristic: Self::astar_heuristic(0, goal_joltages, max_button_size),
+  /// None if the search was cancelled.
+  fn astar_joltages(
+    (_, buttons, goal): &Machine,
+    max_button_size: usize,
+    progress: &Progress,
+  ) -> Option<u16> {
+    let space = JoltagesSpace {
+      goal: *goal,
+      buttons,
+      max_button_size,
+      progress,
     };
-    let mut frontier = BinaryHeap::from([init_node]);
-    let mut reached = FxHashMap::default();
-    reached.insert(0u128, 0u16);
-
-    while let Some(n) = frontier.pop() {
-      if reached.contains_key(&n.joltages) && n.cost > reached[&n.joltages] {
-        continue;
-      }
-      if n.joltages == *goal_joltages {
-        return n.cost;
-      }
-
-      'buttons: for button in buttons {
-        let mut child_joltages = n.joltages;
-        for &i in button {
-          if n.joltages.joltage(i as usize) == goal_joltages.joltage(i as usize) {
-            continue 'buttons;
-          }
-          child_joltages.increment_joltage(i as usize);
-        }
-
-        let child_cost = n.cost + 1;
-        if !reached.contains_key(&child_joltages) || child_cost < reached[&child_joltages] {
-          let child = AStarNode {
-            joltages: child_joltages,
-            cost: child_cost,
-            heuristic: Self::astar_heuristic(child_joltages, goal_joltages, max_button_size),
-          };
-          reached.insert(child_joltages, child_cost);
-          frontier.push(child);
-        }
-      }
-    }
-
-    panic!("No path found");
+    let presses = astar_cost(&space, 0);
+    assert!(
+      presses.is_some() || progress.is_cancelled(),
+      "No path found"
+    );
+    presses
   }
 
   fn astar_heuristic(joltages: Joltages, goal_joltages: &Joltages, max_button_size: usize) -> u16 {
     let mut remaining_max = 0;
     let mut remaining_sum = 0;
-    for i in 0..10 {
+    for i in 0..MAX_COUNTERS {
       let diff = goal_joltages.joltage(i) - joltages.joltage(i);
       remaining_sum += diff;
       if diff > remaining_max {
@@ -168,7 +184,7 @@ impl Day10 {
 
 trait IntJoltages {
   fn joltage(&self, i: usize) -> u16;
-  fn increment_joltage(&mut self, i: usize) -> ();
+  fn increment_joltage(&mut self, i: usize);
 }
 
 impl IntJoltages for u128 {
@@ -176,28 +192,115 @@ impl IntJoltages for u128 {
     ((self >> (i * 9)) & 0x1FF) as u16
   }
 
-  fn increment_joltage(&mut self, i: usize) -> () {
+  fn increment_joltage(&mut self, i: usize) {
     *self += 1u128 << (i * 9);
   }
 }
 
-#[derive(Eq, PartialEq)]
-struct AStarNode {
-  joltages: u128,
-  cost: u16,
-  heuristic: u16,
+/// Each button press toggles the lights it is wired to.
+struct LightsSpace<'a> {
+  goal: Lights,
+  buttons: &'a Buttons,
 }
 
-impl Ord for AStarNode {
-  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-    (other.cost + other.heuristic)
-      .cmp(&(self.cost + self.heuristic))
-      .then_with(|| self.joltages.cmp(&other.joltages))
+impl SearchSpace for LightsSpace<'_> {
+  type State = Lights;
+  type Cost = u16;
+
+  fn successors(&self, &lights: &Lights) -> impl Iterator<Item = (Lights, u16)> {
+    self.buttons.iter().map(move |button| {
+      let toggled = button.iter().fold(lights, |lights, &i| lights ^ (1 << i));
+      (toggled, 1)
+    })
+  }
+
+  fn is_goal(&self, lights: &Lights) -> bool {
+    *lights == self.goal
   }
 }
 
-impl PartialOrd for AStarNode {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-    Some(self.cmp(other))
+/// Each button press increments the counters it is wired to, which may never pass their goal.
+struct JoltagesSpace<'a> {
+  goal: Joltages,
+  buttons: &'a Buttons,
+  max_button_size: usize,
+  progress: &'a Progress,
+}
+
+impl SearchSpace for JoltagesSpace<'_> {
+  type State = Joltages;
+  type Cost = u16;
+
+  fn successors(&self, &joltages: &Joltages) -> impl Iterator<Item = (Joltages, u16)> {
+    self.buttons.iter().filter_map(move |button| {
+      let mut next = joltages;
+      for &i in button {
+        if joltages.joltage(i as usize) == self.goal.joltage(i as usize) {
+          return None;
+        }
+        next.increment_joltage(i as usize);
+      }
+      Some((next, 1))
+    })
+  }
+
+  fn is_goal(&self, joltages: &Joltages) -> bool {
+    *joltages == self.goal
+  }
+
+  fn heuristic(&self, &joltages: &Joltages) -> u16 {
+    Day10::astar_heuristic(joltages, &self.goal, self.max_button_size)
+  }
+
+  fn is_cancelled(&self) -> bool {
+    self.progress.is_cancelled()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_machine() {
+    let machines = Day10::parse("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}\n").unwrap();
+    let (lights, buttons, joltages) = &machines[0];
+    assert_eq!(*lights, 0b0110);
+    assert_eq!(
+      buttons,
+      &vec![vec![3], vec![1, 3], vec![2], vec![2, 3], vec![0, 2], vec![0, 1]]
+    );
+    assert_eq!(
+      (0..4).map(|i| joltages.joltage(i)).collect::<Vec<_>>(),
+      vec![3, 5, 4, 7]
+    );
+  }
+
+  #[test]
+  fn reports_invalid_light_position() {
+    let error = Day10::parse("[.x] (0) {1}\n").unwrap_err();
+    assert!(matches!(
+      error,
+      ParseError::Invalid {
+        line: 1,
+        column: 3,
+        ..
+      }
+    ));
+  }
+
+  #[test]
+  fn rejects_missing_joltages() {
+    assert!(Day10::parse("[.#] (0) (1)\n").is_err());
+  }
+
+  #[test]
+  fn rejects_missing_or_malformed_buttons() {
+    let error = Day10::parse("[.#] x {1,2}\n").unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Day 10, line 1, column 6: expected a button in parentheses (found \"x\")"
+    );
+    assert!(Day10::parse("[.#] {1,2}\n").is_err());
   }
 }
@@ -1,64 +1,109 @@
-use std::{fs::File, io::Read};
-
-use super::Solver;
+use super::{Example, Params, ParseError, Progress, Solver};
+use crate::util::IntervalSet;
 
 pub struct Day2;
 
 impl Solver for Day2 {
-  type ParsedInput = Vec<(u64, u64)>;
+  const DAY: u8 = 2;
+  const TITLE: &'static str = "Gift Shop";
+
+  type ParsedInput = IntervalSet<u64>;
 
   type Output1 = u64;
 
   type Output2 = u64;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    let mut str = String::new();
-    input.read_to_string(&mut str).unwrap();
-    str
-      .split(',')
-      .map(|pair| {
-        let mut nums = pair.split('-');
-        let first = nums.next().unwrap().parse::<u64>().unwrap();
-        let second = nums.next().unwrap().parse::<u64>().unwrap();
-        (first, second)
-      })
-      .collect()
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    let mut ranges = IntervalSet::new();
+    for (i, text) in input.lines().enumerate() {
+      let line = Self::line(i, text);
+      for pair in text.split(',').filter(|pair| !pair.trim().is_empty()) {
+        let mut nums = pair.trim().split('-');
+        let first = line.parse_next::<u64>(nums.next(), "expected range start")?;
+        let second = line.parse_next::<u64>(nums.next(), "expected range end")?;
+        if let Some(extra) = nums.next() {
+          return Err(line.error(extra, "unexpected third range bound"));
+        }
+        ranges.insert(first..=second);
+      }
+    }
+    Ok(ranges)
   }
 
   fn part_1_test_output() -> Option<Self::Output1> {
     Some(1227775554)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![
+      Example::inline("11-22", 33),
+      Example::inline("95-115", 99),
+      Example::inline("998-1012", 1010),
+    ]
+  }
+
   fn part_1(parsed_input: &Self::ParsedInput) -> Self::Output1 {
-    let mut sum: u64 = 0;
-    for (start, end) in parsed_input {
-      for num in *start..=*end {
-        if Self::is_repeated_once(num) {
-          sum += num;
-        }
-      }
-    }
-    sum
+    Self::part_1_with(parsed_input, &Params::default(), &Progress::disabled())
+  }
+
+  fn part_1_with(
+    parsed_input: &Self::ParsedInput,
+    _params: &Params,
+    progress: &Progress,
+  ) -> Self::Output1 {
+    Self::sum_matching(parsed_input, progress, Self::is_repeated_once)
   }
 
   fn part_2_test_output() -> Option<Self::Output2> {
     Some(4174379265)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![
+      Example::inline("11-22", 33),
+      Example::inline("95-115", 210),
+      Example::inline("998-1012", 2009),
+    ]
+  }
+
   fn part_2(parsed_input: &Self::ParsedInput) -> Self::Output2 {
-    let mut sum: u64 = 0;
-    for (start, end) in parsed_input {
-      for num in *start..=*end {
-        if Self::is_repeated(num) {
+    Self::part_2_with(parsed_input, &Params::default(), &Progress::disabled())
+  }
+
+  fn part_2_with(
+    parsed_input: &Self::ParsedInput,
+    _params: &Params,
+    progress: &Progress,
+  ) -> Self::Output2 {
+    Self::sum_matching(parsed_input, progress, Self::is_repeated)
+  }
+}
+
+impl Day2 {
+  fn sum_matching(ranges: &IntervalSet<u64>, progress: &Progress, matches: fn(u64) -> bool) -> u64 {
+    let total = ranges.covered_len();
+    let mut checked: u128 = 0;
+    let mut sum = 0;
+    progress.set_label("IDs");
+    for range in ranges {
+      for num in range.clone() {
+        if matches(num) {
           sum += num;
         }
+        checked += 1;
+        // Often enough to give up quickly after a timeout, rarely enough to be free
+        if checked.is_multiple_of(0x10000) {
+          if progress.is_cancelled() {
+            return sum;
+          }
+          let clamp = |n: u128| usize::try_from(n).unwrap_or(usize::MAX);
+          progress.set(clamp(checked), clamp(total));
+        }
       }
     }
     sum
   }
-}
 
-impl Day2 {
   // fn is_repeated_once(num: u64) -> bool {
   //   let str = num.to_string();
   //   if str.len() % 2 != 0 {
@@ -70,8 +115,11 @@ impl Day2 {
 
   // Much better :)
   fn is_repeated_once(num: u64) -> bool {
-    let len = num.ilog10() + 1;
-    if len % 2 != 0 {
+    // 0 has a single digit, so nothing to repeat
+    let Some(len) = num.checked_ilog10().map(|log| log + 1) else {
+      return false;
+    };
+    if !len.is_multiple_of(2) {
       return false;
     }
 
@@ -91,3 +139,24 @@ impl Day2 {
     false
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn handles_ranges_starting_at_zero() {
+    let ranges = Day2::parse("0-11").unwrap();
+    assert_eq!(Day2::part_1(&ranges), 11);
+    assert_eq!(Day2::part_2(&ranges), 11);
+  }
+
+  #[test]
+  fn rejects_a_third_range_bound() {
+    let error = Day2::parse("11-22,1-2-3").unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Day 2, line 1, column 11: unexpected third range bound (found \"3\")"
+    );
+  }
+}
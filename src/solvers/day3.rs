@@ -1,24 +1,37 @@
-use std::{
-  fs::File,
-  io::{self, BufRead},
-};
-
-use super::Solver;
+use super::{Example, ParseError, Solver};
 
 pub struct Day3;
 
 impl Solver for Day3 {
+  const DAY: u8 = 3;
+  const TITLE: &'static str = "Lobby";
+
   type ParsedInput = Vec<Vec<u32>>;
 
   type Output1 = u32;
 
   type Output2 = u64;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    io::BufReader::new(input)
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    input
       .lines()
-      .flatten()
-      .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
+      .enumerate()
+      .map(|(i, text)| {
+        let line = Self::line(i, text);
+        if text.is_empty() {
+          return Err(line.missing("expected battery digits"));
+        }
+        if text.len() < 12 {
+          return Err(line.error(text, "expected at least 12 battery digits"));
+        }
+        text
+          .char_indices()
+          .map(|(j, c)| {
+            c.to_digit(10)
+              .ok_or_else(|| line.error(&text[j..j + c.len_utf8()], "expected battery digit"))
+          })
+          .collect()
+      })
       .collect()
   }
 
@@ -26,6 +39,15 @@ impl Solver for Day3 {
     Some(357)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![
+      Example::inline("987654321111111", 98),
+      Example::inline("811111111111119", 89),
+      Example::inline("234234234234278", 78),
+      Example::inline("818181911112111", 92),
+    ]
+  }
+
   fn part_1(banks: &Self::ParsedInput) -> Self::Output1 {
     let mut sum = 0;
 
@@ -64,6 +86,16 @@ impl Solver for Day3 {
     Some(3121910778619)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![
+      Example::inline("987654321111111", 987654321111),
+      Example::inline("811111111111119", 811111111119),
+      Example::inline("234234234234278", 434234234278),
+      Example::inline("818181911112111", 888911112111),
+    ]
+  }
+
+  /// Every bank has at least 12 batteries, checked by `parse`.
   fn part_2(banks: &Self::ParsedInput) -> Self::Output2 {
     let mut sum = 0;
 
@@ -97,3 +129,17 @@ impl Solver for Day3 {
 }
 
 impl Day3 {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejects_short_banks() {
+    let error = Day3::parse("12345\n").unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Day 3, line 1, column 1: expected at least 12 battery digits (found \"12345\")"
+    );
+  }
+}
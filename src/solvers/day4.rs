@@ -1,40 +1,54 @@
-use std::{
-  collections::{HashSet, VecDeque},
-  fs::File,
-  io::{self, BufRead},
-};
+use std::collections::VecDeque;
 
-use super::Solver;
+use super::{Example, ParseError, Solver};
+use crate::util::{Grid, Point};
 
 pub struct Day4;
 
+const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
 impl Solver for Day4 {
-  type ParsedInput = HashSet<(u8, u8)>;
+  const DAY: u8 = 4;
+  const TITLE: &'static str = "Printing Department";
+
+  /// True where there is a roll of paper
+  type ParsedInput = Grid<bool>;
 
   type Output1 = u32;
 
   type Output2 = u32;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    let mut paper_rolls = HashSet::new();
-    for (row, line) in io::BufReader::new(input).lines().flatten().enumerate() {
-      for (col, char) in line.chars().enumerate() {
-        if char == '@' {
-          paper_rolls.insert((col as u8 + 1, row as u8 + 1));
-        }
-      }
-    }
-    paper_rolls
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    Grid::parse(Self::DAY, input, |c| match c {
+      '@' => Ok(true),
+      '.' => Ok(false),
+      _ => Err("expected '@' or '.'"),
+    })
   }
 
   fn part_1_test_output() -> Option<Self::Output1> {
     Some(13)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 13)]
+  }
+
   fn part_1(rolls: &Self::ParsedInput) -> Self::Output1 {
     rolls
       .iter()
-      .filter(|(x, y)| Self::neighbor_rolls(rolls, *x, *y).len() < 4)
+      .filter(|(point, roll)| **roll && Self::neighbor_rolls(rolls, *point).count() < 4)
       .count() as u32
   }
 
@@ -42,14 +56,18 @@ impl Solver for Day4 {
     Some(43)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 43)]
+  }
+
   fn part_2(rolls: &Self::ParsedInput) -> Self::Output2 {
     let mut count = 0;
-    let mut q: VecDeque<_> = rolls.iter().copied().collect();
+    let mut q: VecDeque<_> = rolls.find_all(&true).collect();
     let mut rolls = rolls.clone();
-    while !q.is_empty() {
-      let (x, y) = q.pop_front().unwrap();
-      let neighbors = Self::neighbor_rolls(&rolls, x, y);
-      if neighbors.len() < 4 && rolls.remove(&(x, y)) {
+    while let Some(point) = q.pop_front() {
+      let neighbors: Vec<_> = Self::neighbor_rolls(&rolls, point).collect();
+      if neighbors.len() < 4 && rolls[point] {
+        rolls[point] = false;
         count += 1;
         q.extend(neighbors);
       }
@@ -59,29 +77,7 @@ impl Solver for Day4 {
 }
 
 impl Day4 {
-  const NEIGHBOR_OFFSETS: [(i16, i16); 8] = [
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-    (-1, 0),
-  ];
-
-  fn neighbor_rolls(rolls: &HashSet<(u8, u8)>, x: u8, y: u8) -> Vec<(u8, u8)> {
-    Self::NEIGHBOR_OFFSETS
-      .iter()
-      .filter_map(|(dx, dy)| {
-        let nx = x as i16 + *dx;
-        let ny = y as i16 + *dy;
-        let neighbor = (nx as u8, ny as u8);
-        if rolls.contains(&neighbor) {
-          return Some(neighbor);
-        }
-        None
-      })
-      .collect()
+  fn neighbor_rolls(rolls: &Grid<bool>, point: Point) -> impl Iterator<Item = Point> + '_ {
+    rolls.neighbors_8(point).filter(|neighbor| rolls[*neighbor])
   }
 }
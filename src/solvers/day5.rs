@@ -1,77 +1,80 @@
-use std::{
-  fs::File,
-  io::{self, BufRead},
-  ops::RangeInclusive,
-};
-
-use super::Solver;
+use super::{Example, ParseError, Solver};
+use crate::util::IntervalSet;
 
 pub struct Day5;
 
+const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
 impl Solver for Day5 {
-  type ParsedInput = (Vec<RangeInclusive<u64>>, Vec<u64>);
+  const DAY: u8 = 5;
+  const TITLE: &'static str = "Cafeteria";
+
+  type ParsedInput = (IntervalSet<u64>, Vec<u64>);
 
   type Output1 = u64;
 
-  type Output2 = u64;
+  type Output2 = u128;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    let mut ranges = Vec::new();
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    let mut ranges = IntervalSet::new();
     let mut ids = Vec::new();
     let mut reading_ranges = true;
-    for line in io::BufReader::new(input).lines().flatten() {
-      if line.is_empty() {
+    for (i, text) in input.lines().enumerate() {
+      if text.is_empty() {
         reading_ranges = false;
         continue;
       }
 
+      let line = Self::line(i, text);
       if reading_ranges {
-        let parts: Vec<&str> = line.split('-').collect();
-        let start: u64 = parts[0].parse().unwrap();
-        let end: u64 = parts[1].parse().unwrap();
-        ranges.push(start..=end);
+        let mut parts = text.split('-');
+        let start: u64 = line.parse_next(parts.next(), "expected range start")?;
+        let end: u64 = line.parse_next(parts.next(), "expected range end")?;
+        if let Some(extra) = parts.next() {
+          return Err(line.error(extra, "unexpected third range bound"));
+        }
+        ranges.insert(start..=end);
       } else {
-        let id: u64 = line.parse().unwrap();
+        let id: u64 = line.parse(text, "expected ingredient ID")?;
         ids.push(id);
       }
     }
-    (ranges, ids)
+    Ok((ranges, ids))
   }
 
   fn part_1_test_output() -> Option<Self::Output1> {
     Some(3)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 3)]
+  }
+
   fn part_1((ranges, ids): &Self::ParsedInput) -> Self::Output1 {
-    ids.iter().fold(0, |acc, id| {
-      if ranges.iter().any(|r| r.contains(id)) {
-        acc + 1
-      } else {
-        acc
-      }
-    })
+    ids.iter().filter(|&&id| ranges.contains(id)).count() as u64
   }
 
   fn part_2_test_output() -> Option<Self::Output2> {
     Some(14)
   }
 
-  fn part_2((ranges, _): &Self::ParsedInput) -> Self::Output2 {
-    let mut ranges = ranges.iter().cloned().collect::<Vec<_>>();
-    ranges.sort_by_key(|r| *r.start());
-
-    let mut new_ranges = vec![ranges[0].clone()];
-    for r in ranges {
-      let last = new_ranges.last_mut().unwrap();
-      if r.start() <= last.end() {
-        *last = *last.start()..=*last.end().max(r.end());
-        continue;
-      }
-      new_ranges.push(r.clone());
-    }
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 14)]
+  }
 
-    new_ranges
-      .iter()
-      .fold(0, |acc, r| acc + (r.end() - r.start() + 1))
+  fn part_2((ranges, _): &Self::ParsedInput) -> Self::Output2 {
+    ranges.covered_len()
   }
 }
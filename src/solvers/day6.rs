@@ -1,67 +1,113 @@
-use std::{fs::File, io::Read, str::FromStr};
+use std::str::FromStr;
 
-use super::Solver;
+use super::{Example, ParseError, Solver};
 
 pub struct Day6;
 
+const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+
 impl Solver for Day6 {
+  const DAY: u8 = 6;
+  const TITLE: &'static str = "Trash Compactor";
+
   type ParsedInput = Vec<(Operator, Vec<String>)>;
 
   type Output1 = u64;
 
   type Output2 = u64;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    let mut str = String::new();
-    input.read_to_string(&mut str).unwrap();
-    let rows = str.lines().count();
-    let cols = str
-      .lines()
-      .next()
-      .unwrap()
-      .trim()
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    for (row, text) in lines.iter().enumerate() {
+      if let Some(col) = text.chars().position(|c| !c.is_ascii()) {
+        return Err(Self::line(row, text).error_at(col + 1, "unexpected character"));
+      }
+    }
+
+    let (operator_text, number_lines) = match lines.split_last() {
+      Some((operator_text, number_lines)) if !number_lines.is_empty() => {
+        (operator_text, number_lines)
+      }
+      _ => {
+        let text = lines.first().copied().unwrap_or_default();
+        return Err(Self::line(0, text).missing("expected rows of numbers followed by operators"));
+      }
+    };
+
+    let operator_line = Self::line(number_lines.len(), operator_text);
+    let operators = operator_text
       .split_whitespace()
-      .count();
-
-    let mut number_cols: Vec<Vec<String>> = vec![vec![String::new(); rows - 1]; cols];
-    let mut operators: Vec<Operator> = Vec::new();
-
-    for (row, line) in str.lines().enumerate() {
-      if row == rows - 1 {
-        operators = line
-          .trim()
-          .split_whitespace()
-          .map(|s| s.parse().unwrap())
-          .collect();
-      } else {
-        let mut split_indices = Vec::new();
-        for (col, char) in line.chars().enumerate() {
-          if char.is_whitespace()
-            && str
-              .lines()
-              .all(|l| l.chars().nth(col).unwrap().is_whitespace())
-          {
-            split_indices.push(col);
-          }
+      .map(|op| operator_line.parse::<Operator>(op, "expected operator + or *"))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    // Columns are separated wherever every line (missing trailing characters
+    // count as whitespace) has a space in the same position
+    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let mut split_indices: Vec<usize> = (0..width)
+      .filter(|&col| {
+        lines.iter().all(|l| {
+          l.as_bytes()
+            .get(col)
+            .is_none_or(|c| c.is_ascii_whitespace())
+        })
+      })
+      .collect();
+    split_indices.push(width);
+
+    let mut number_cols: Vec<Vec<String>> = vec![Vec::new(); operators.len()];
+    for (row, text) in number_lines.iter().enumerate() {
+      let line = Self::line(row, text);
+      let padded = format!("{:width$}", text);
+
+      let mut col = 0;
+      let mut last_split = 0;
+      for &split_index in &split_indices {
+        let num_str = &padded[last_split..split_index];
+        let num_start = last_split;
+        last_split = split_index + 1;
+        if num_str.is_empty() {
+          continue;
         }
-        split_indices.push(line.len());
 
-        let mut last_split = 0;
-        for (col, &split_index) in split_indices.iter().enumerate() {
-          let num_str = line[last_split..split_index].to_string();
-          number_cols[col][row] = num_str;
-          last_split = split_index + 1;
+        if num_str.trim().parse::<u64>().is_err() {
+          return Err(if num_start < text.len() {
+            line.error(
+              text[num_start..split_index.min(text.len())].trim(),
+              "expected number",
+            )
+          } else {
+            line.missing("expected number")
+          });
+        }
+        if col < operators.len() {
+          number_cols[col].push(num_str.to_string());
         }
+        col += 1;
+      }
+
+      if col != operators.len() {
+        return Err(line.error(
+          text,
+          &format!(
+            "expected {} numbers to match the operators, found {}",
+            operators.len(),
+            col
+          ),
+        ));
       }
     }
 
-    operators.into_iter().zip(number_cols).collect()
+    Ok(operators.into_iter().zip(number_cols).collect())
   }
 
   fn part_1_test_output() -> Option<Self::Output1> {
     Some(4277556)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 4277556)]
+  }
+
   fn part_1(cols: &Self::ParsedInput) -> Self::Output1 {
     cols.iter().fold(0, |acc, (op, nums)| {
       let init = match op {
@@ -79,6 +125,10 @@ impl Solver for Day6 {
     Some(3263827)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 3263827)]
+  }
+
   fn part_2(cols: &Self::ParsedInput) -> Self::Output2 {
     let mut new_num_cols: Vec<Vec<String>> = vec![vec![]; cols.len()];
     for (col, (_, nums)) in cols.iter().enumerate() {
@@ -92,7 +142,7 @@ impl Solver for Day6 {
     Self::part_1(
       &cols
         .iter()
-        .zip(new_num_cols.into_iter())
+        .zip(new_num_cols)
         .map(|((op, _), nums)| (op.clone(), nums))
         .collect(),
     )
@@ -125,3 +175,38 @@ impl Operator {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_aligned_columns() {
+    let cols = Day6::parse(EXAMPLE).unwrap();
+    assert_eq!(cols.len(), 4);
+    assert_eq!(cols[0].1, vec!["123", " 45", "  6"]);
+    assert_eq!(cols[3].1, vec!["64 ", "23 ", "314"]);
+  }
+
+  #[test]
+  fn pads_lines_without_trailing_whitespace() {
+    let trimmed: String = EXAMPLE
+      .lines()
+      .map(|l| format!("{}\n", l.trim_end()))
+      .collect();
+    assert_eq!(Day6::part_2(&Day6::parse(&trimmed).unwrap()), 3263827);
+  }
+
+  #[test]
+  fn reports_invalid_operator_position() {
+    let error = Day6::parse("1 2\n+ -\n").unwrap_err();
+    assert!(matches!(
+      error,
+      ParseError::Invalid {
+        line: 2,
+        column: 3,
+        ..
+      }
+    ));
+  }
+}
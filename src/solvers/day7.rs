@@ -1,56 +1,87 @@
-use std::{
-  collections::{HashMap, HashSet},
-  fs::File,
-  io::BufRead,
-};
+use std::collections::{HashMap, HashSet};
 
-use super::Solver;
+use super::{Example, ParseError, Solver};
+use crate::util::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+  Start,
+  Splitter,
+  Empty,
+}
 
 pub struct Day7;
 
+const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
 impl Solver for Day7 {
-  type ParsedInput = (u16, Vec<HashSet<u16>>, u16);
+  const DAY: u8 = 7;
+  const TITLE: &'static str = "Laboratories";
+
+  /// Column of the start and the manifold
+  type ParsedInput = (usize, Grid<Cell>);
 
   type Output1 = u32;
 
   type Output2 = u64;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    let mut start = 0;
-    let mut splitter_rows = Vec::new();
-    let mut rows = 0;
-    for (y, line) in std::io::BufReader::new(input).lines().flatten().enumerate() {
-      splitter_rows.push(HashSet::new());
-      for (x, c) in line.chars().enumerate() {
-        if c == 'S' {
-          start = x as u16;
-        } else if c == '^' {
-          splitter_rows[y].insert(x as u16);
-        }
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    let mut found_start = false;
+    let grid = Grid::parse(Self::DAY, input, |c| match c {
+      'S' if !found_start => {
+        found_start = true;
+        Ok(Cell::Start)
       }
-      rows += 1;
+      'S' => Err("found a second start 'S'"),
+      '^' => Ok(Cell::Splitter),
+      '.' => Ok(Cell::Empty),
+      _ => Err("expected 'S', '^' or '.'"),
+    })?;
+
+    match grid.find(&Cell::Start) {
+      Some((x, _)) => Ok((x, grid)),
+      None => Err(Self::line(0, "").missing("expected a start 'S'")),
     }
-    (start, splitter_rows, rows)
   }
 
   fn part_1_test_output() -> Option<Self::Output1> {
     Some(21)
   }
 
-  fn part_1((start, splitter_rows, rows): &Self::ParsedInput) -> Self::Output1 {
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 21)]
+  }
+
+  fn part_1((start, grid): &Self::ParsedInput) -> Self::Output1 {
     let mut split_count = 0;
-    let mut beams: HashSet<u16> = HashSet::new();
-    beams.insert(*start);
+    let mut beams: HashSet<isize> = HashSet::new();
+    beams.insert(*start as isize);
 
-    for y in 1..*rows {
-      let splitters = &splitter_rows[y as usize];
-      if splitters.is_empty() {
+    for row in grid.rows().skip(1) {
+      if !row.contains(&Cell::Splitter) {
         continue;
       }
 
-      let mut next_beams: HashSet<u16> = HashSet::new();
+      let mut next_beams: HashSet<isize> = HashSet::new();
       for beam in beams {
-        if splitters.contains(&beam) {
+        if Self::splits(row, beam) {
           split_count += 1;
           next_beams.insert(beam - 1);
           next_beams.insert(beam + 1);
@@ -68,19 +99,22 @@ impl Solver for Day7 {
     Some(40)
   }
 
-  fn part_2((start, splitter_rows, rows): &Self::ParsedInput) -> Self::Output2 {
-    let mut beam_timelines: HashMap<u16, u64> = HashMap::new();
-    beam_timelines.insert(*start, 1);
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 40)]
+  }
+
+  fn part_2((start, grid): &Self::ParsedInput) -> Self::Output2 {
+    let mut beam_timelines: HashMap<isize, u64> = HashMap::new();
+    beam_timelines.insert(*start as isize, 1);
 
-    for y in 1..*rows {
-      let splitters = &splitter_rows[y as usize];
-      if splitters.is_empty() {
+    for row in grid.rows().skip(1) {
+      if !row.contains(&Cell::Splitter) {
         continue;
       }
 
-      let mut next_beam_timelines: HashMap<u16, u64> = HashMap::new();
+      let mut next_beam_timelines: HashMap<isize, u64> = HashMap::new();
       for (beam, count) in beam_timelines {
-        if splitters.contains(&beam) {
+        if Self::splits(row, beam) {
           next_beam_timelines
             .entry(beam - 1)
             .and_modify(|c| *c += count)
@@ -102,3 +136,30 @@ impl Solver for Day7 {
     beam_timelines.values().sum()
   }
 }
+
+impl Day7 {
+  /// Beams are columns, which go negative or past the width when a splitter on the edge
+  /// sends a beam outside the grid. There it goes straight down.
+  fn splits(row: &[Cell], beam: isize) -> bool {
+    usize::try_from(beam)
+      .ok()
+      .and_then(|x| row.get(x))
+      .is_some_and(|cell| *cell == Cell::Splitter)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn beams_leaving_the_grid_go_straight_down() {
+    let manifold = Day7::parse("..S\n..^\n^..\n").unwrap();
+    assert_eq!(Day7::part_1(&manifold), 1);
+    assert_eq!(Day7::part_2(&manifold), 2);
+
+    let manifold = Day7::parse("S..\n^..\n..^\n").unwrap();
+    assert_eq!(Day7::part_1(&manifold), 1);
+    assert_eq!(Day7::part_2(&manifold), 2);
+  }
+}
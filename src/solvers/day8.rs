@@ -1,33 +1,57 @@
-use std::{
-  collections::HashMap,
-  fs::File,
-  io::{self, BufRead},
-};
-
-use super::Solver;
+use super::{Example, Param, Params, ParseError, Progress, Solver};
+use crate::util::UnionFind;
 
 pub struct Day8;
 
+const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
+
 impl Solver for Day8 {
+  const DAY: u8 = 8;
+  const TITLE: &'static str = "Playground";
+  const PARAMS: &'static [Param] = &[Param::new("connections", 10, 1000).range(0, u32::MAX as i64)];
+
   type ParsedInput = Vec<Point>;
 
   type Output1 = u64;
 
   type Output2 = u32;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    io::BufReader::new(input)
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    input
       .lines()
-      .flatten()
-      .map(|line| {
-        let coords = line
-          .split(',')
-          .map(|num| num.parse::<u32>().unwrap())
-          .collect::<Vec<u32>>();
-        Point {
-          x: coords[0],
-          y: coords[1],
-          z: coords[2],
+      .enumerate()
+      .map(|(i, text)| {
+        let line = Self::line(i, text);
+        let mut coords = text.split(',');
+        let point = Point {
+          x: line.parse_next(coords.next(), "expected X coordinate")?,
+          y: line.parse_next(coords.next(), "expected Y coordinate")?,
+          z: line.parse_next(coords.next(), "expected Z coordinate")?,
+        };
+        match coords.next() {
+          Some(extra) => Err(line.error(extra, "unexpected fourth coordinate")),
+          None => Ok(point),
         }
       })
       .collect()
@@ -37,35 +61,42 @@ impl Solver for Day8 {
     Some(40)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 40)]
+  }
+
   fn part_1(boxes: &Self::ParsedInput) -> Self::Output1 {
-    Self::part_1_flag(boxes, false)
+    Self::part_1_with(boxes, &Params::real(Self::PARAMS), &Progress::disabled())
   }
 
-  fn part_1_flag(boxes: &Self::ParsedInput, is_test: bool) -> Self::Output1 {
-    let mut circuits = DisjointSetForest::new(boxes.len());
-    let count = if is_test { 10 } else { 1000 };
+  fn part_1_with(
+    boxes: &Self::ParsedInput,
+    params: &Params,
+    _progress: &Progress,
+  ) -> Self::Output1 {
+    let mut circuits = UnionFind::new(boxes.len());
+    let count: usize = params.get("connections");
     for &((ai, _), (bi, _)) in Self::make_pairs(boxes).iter().take(count) {
       circuits.union(ai, bi);
     }
 
-    let mut circuits = Vec::from(circuits);
-    circuits.sort_by_key(|c| c.len());
-    circuits.reverse();
-    circuits
-      .iter()
-      .take(3)
-      .fold(1, |acc, c| acc * (c.len() as u64))
+    let mut sizes: Vec<usize> = circuits.groups().map(|c| c.len()).collect();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes.iter().take(3).fold(1, |acc, &size| acc * size as u64)
   }
 
   fn part_2_test_output() -> Option<Self::Output2> {
     Some(25272)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 25272)]
+  }
+
   fn part_2(boxes: &Self::ParsedInput) -> Self::Output2 {
-    let mut circuits = DisjointSetForest::new(boxes.len());
+    let mut circuits = UnionFind::new(boxes.len());
     for &((ai, a), (bi, b)) in Self::make_pairs(boxes).iter() {
-      circuits.union(ai, bi);
-      if circuits.sizes.iter().any(|n| *n == boxes.len()) {
+      if circuits.union(ai, bi) && circuits.components() == 1 {
         return a.x * b.x;
       }
     }
@@ -73,8 +104,10 @@ impl Solver for Day8 {
   }
 }
 
+type IndexedPair<'a> = ((usize, &'a Point), (usize, &'a Point));
+
 impl Day8 {
-  fn make_pairs(boxes: &Vec<Point>) -> Vec<((usize, &Point), (usize, &Point))> {
+  fn make_pairs(boxes: &[Point]) -> Vec<IndexedPair<'_>> {
     let mut pairs = boxes
       .iter()
       .enumerate()
@@ -108,49 +141,3 @@ impl Point {
       .sqrt()
   }
 }
-
-struct DisjointSetForest {
-  parents: Vec<usize>,
-  sizes: Vec<usize>,
-}
-
-impl DisjointSetForest {
-  fn new(n: usize) -> Self {
-    Self {
-      parents: (0..n).collect(),
-      sizes: vec![1; n],
-    }
-  }
-
-  fn find(&mut self, x: usize) -> usize {
-    if self.parents[x] != x {
-      return self.find(self.parents[x]);
-    }
-    self.parents[x]
-  }
-
-  fn union(&mut self, a: usize, b: usize) {
-    let root_a = self.find(a);
-    let root_b = self.find(b);
-    if root_a != root_b {
-      if self.sizes[root_a] >= self.sizes[root_b] {
-        self.parents[root_b] = root_a;
-        self.sizes[root_a] += self.sizes[root_b];
-      } else {
-        self.parents[root_a] = root_b;
-        self.sizes[root_b] += self.sizes[root_a];
-      }
-    }
-  }
-}
-
-impl From<DisjointSetForest> for Vec<Vec<usize>> {
-  fn from(mut dsu: DisjointSetForest) -> Self {
-    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
-    for i in 0..dsu.parents.len() {
-      let root = dsu.find(i);
-      groups.entry(root).or_default().push(i);
-    }
-    groups.into_values().collect()
-  }
-}
@@ -1,36 +1,62 @@
-use std::{
-  fs::File,
-  io::{self, BufRead},
-};
-
-use super::Solver;
+use super::{Example, ParseError, Solver};
 
 pub struct Day9;
 
+const EXAMPLE: &str = "\
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+
 impl Solver for Day9 {
+  const DAY: u8 = 9;
+  const TITLE: &'static str = "Movie Theater";
+
   type ParsedInput = Vec<(u32, u32)>;
 
   type Output1 = u64;
 
   type Output2 = u32;
 
-  fn parse(input: &mut File) -> Self::ParsedInput {
-    io::BufReader::new(input)
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    let red_tiles = input
       .lines()
-      .flatten()
-      .map(|line| {
-        let mut parts = line.split(',');
-        let x = parts.next().unwrap().parse::<u32>().unwrap();
-        let y = parts.next().unwrap().parse::<u32>().unwrap();
-        (x, y)
+      .enumerate()
+      .map(|(i, text)| {
+        let line = Self::line(i, text);
+        let mut parts = text.split(',');
+        let x = line.parse_next::<u32>(parts.next(), "expected X coordinate")?;
+        let y = line.parse_next::<u32>(parts.next(), "expected Y coordinate")?;
+        match parts.next() {
+          Some(extra) => Err(line.error(extra, "unexpected third coordinate")),
+          None => Ok((x, y)),
+        }
       })
-      .collect()
+      .collect::<Result<Vec<_>, _>>()?;
+
+    // Part 1 needs a pair of opposite corners
+    if red_tiles.len() < 2 {
+      let last = input.lines().count().saturating_sub(1);
+      return Err(
+        Self::line(last, input.lines().last().unwrap_or("")).missing("expected at least two red tiles"),
+      );
+    }
+    Ok(red_tiles)
   }
 
   fn part_1_test_output() -> Option<Self::Output1> {
     Some(50)
   }
 
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    vec![Example::inline(EXAMPLE, 50)]
+  }
+
   fn part_1(red_tiles: &Self::ParsedInput) -> Self::Output1 {
     let mut pairs = red_tiles
       .iter()
@@ -46,6 +72,10 @@ impl Solver for Day9 {
     Some(0)
   }
 
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    vec![Example::inline(EXAMPLE, 0)]
+  }
+
   fn part_2(_red_tiles: &Self::ParsedInput) -> Self::Output2 {
     0
   }
@@ -53,6 +83,27 @@ impl Solver for Day9 {
 
 impl Day9 {
   fn area((ax, ay): (u32, u32), (bx, by): (u32, u32)) -> u64 {
-    (ax as i64 - bx as i64 + 1).abs() as u64 * (ay as i64 - by as i64 + 1).abs() as u64
+    (ax as i64 - bx as i64 + 1).unsigned_abs() * (ay as i64 - by as i64 + 1).unsigned_abs()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejects_fewer_than_two_tiles() {
+    assert!(Day9::parse("").is_err());
+    assert!(Day9::parse("7,1\n").is_err());
+    assert!(Day9::parse("7,1\n11,7\n").is_ok());
+  }
+
+  #[test]
+  fn rejects_a_third_coordinate() {
+    let error = Day9::parse("7,1\n11,7,3\n").unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Day 9, line 2, column 6: unexpected third coordinate (found \"3\")"
+    );
   }
 }
@@ -0,0 +1,83 @@
+use std::fmt;
+
+use super::{Part, style};
+
+pub enum ExampleInput {
+  /// File name relative to the day's input directory
+  File(String),
+  Inline(&'static str),
+}
+
+impl fmt::Display for ExampleInput {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ExampleInput::File(name) => write!(f, "{}", name),
+      ExampleInput::Inline(text) if text.len() <= 24 && !text.trim().contains('\n') => {
+        write!(f, "inline {:?}", text.trim())
+      }
+      ExampleInput::Inline(_) => write!(f, "inline"),
+    }
+  }
+}
+
+pub struct Example<T> {
+  pub input: ExampleInput,
+  pub expected: T,
+}
+
+impl<T> Example<T> {
+  pub fn file(name: &str, expected: T) -> Self {
+    Self {
+      input: ExampleInput::File(name.to_string()),
+      expected,
+    }
+  }
+
+  pub fn inline(input: &'static str, expected: T) -> Self {
+    Self {
+      input: ExampleInput::Inline(input),
+      expected,
+    }
+  }
+
+  pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Example<U> {
+    Example {
+      input: self.input,
+      expected: f(self.expected),
+    }
+  }
+}
+
+pub struct ExampleResult {
+  pub part: Part,
+  pub input: ExampleInput,
+  pub expected: String,
+  pub actual: String,
+}
+
+impl ExampleResult {
+  pub fn passed(&self) -> bool {
+    self.expected == self.actual
+  }
+
+  pub fn print(&self) {
+    if self.passed() {
+      println!(
+        "Example part {} ({}): {} ({})",
+        self.part,
+        self.input,
+        style::green("passed"),
+        self.actual
+      );
+    } else {
+      println!(
+        "Example part {} ({}): {} (expected {}, got {})",
+        self.part,
+        self.input,
+        style::red("FAILED"),
+        self.expected,
+        self.actual
+      );
+    }
+  }
+}
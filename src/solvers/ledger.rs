@@ -0,0 +1,208 @@
+use std::{fmt, fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use super::Part;
+
+/// Feedback given with a rejected answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+  TooHigh,
+  TooLow,
+}
+
+impl fmt::Display for Hint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Hint::TooHigh => write!(f, "too high"),
+      Hint::TooLow => write!(f, "too low"),
+    }
+  }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Entry {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  accepted: Option<String>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  rejected: Vec<Rejected>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Rejected {
+  answer: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  hint: Option<Hint>,
+}
+
+/// Answers submitted for a day's real input, stored as `answers.json` next to it.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Ledger {
+  #[serde(default)]
+  part_1: Entry,
+  #[serde(default)]
+  part_2: Entry,
+}
+
+/// How an answer compares to the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+  Matches,
+  /// Holds the recorded answer
+  Mismatch(String),
+  Rejected(Option<Hint>),
+  /// On the wrong side of a rejected answer with a hint, which is held
+  Beyond(Hint, String),
+}
+
+impl Verdict {
+  pub fn is_match(&self) -> bool {
+    *self == Verdict::Matches
+  }
+}
+
+impl fmt::Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Verdict::Matches => write!(f, "matches recorded answer"),
+      Verdict::Mismatch(recorded) => write!(f, "MISMATCH, recorded answer is {}", recorded),
+      Verdict::Rejected(None) => write!(f, "REJECTED before"),
+      Verdict::Rejected(Some(hint)) => write!(f, "REJECTED before as {}", hint),
+      Verdict::Beyond(hint, rejected) => {
+        write!(
+          f,
+          "{}, {} was rejected as {}",
+          hint.to_string().to_uppercase(),
+          rejected,
+          hint
+        )
+      }
+    }
+  }
+}
+
+impl Ledger {
+  pub fn path(day_path: &str) -> String {
+    format!("{}/answers.json", day_path)
+  }
+
+  /// A missing file is an empty ledger.
+  pub fn load(path: &str) -> Result<Self, String> {
+    let json = match fs::read_to_string(path) {
+      Ok(json) => json,
+      Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+      Err(e) => return Err(format!("Could not read {}: {}", path, e)),
+    };
+    serde_json::from_str(&json).map_err(|e| format!("Invalid answer ledger {}: {}", path, e))
+  }
+
+  pub fn save(&self, path: &str) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+    fs::write(path, json + "\n").map_err(|e| format!("Could not write {}: {}", path, e))
+  }
+
+  /// None if nothing is known about the part or the answer.
+  pub fn check(&self, part: Part, answer: &str) -> Option<Verdict> {
+    let entry = self.entry(part);
+    if let Some(accepted) = &entry.accepted {
+      return Some(if accepted == answer {
+        Verdict::Matches
+      } else {
+        Verdict::Mismatch(accepted.clone())
+      });
+    }
+    if let Some(rejected) = entry.rejected.iter().find(|r| r.answer == answer) {
+      return Some(Verdict::Rejected(rejected.hint));
+    }
+
+    let value: i128 = answer.parse().ok()?;
+    entry.rejected.iter().find_map(|rejected| {
+      let bound: i128 = rejected.answer.parse().ok()?;
+      match rejected.hint? {
+        Hint::TooHigh if value > bound => {
+          Some(Verdict::Beyond(Hint::TooHigh, rejected.answer.clone()))
+        }
+        Hint::TooLow if value < bound => {
+          Some(Verdict::Beyond(Hint::TooLow, rejected.answer.clone()))
+        }
+        _ => None,
+      }
+    })
+  }
+
+  pub fn record(&mut self, part: Part, answer: &str) {
+    let entry = self.entry_mut(part);
+    entry.accepted = Some(answer.to_string());
+    entry.rejected.retain(|r| r.answer != answer);
+  }
+
+  /// Returns false if the answer is the accepted one.
+  pub fn reject(&mut self, part: Part, answer: &str, hint: Option<Hint>) -> bool {
+    let entry = self.entry_mut(part);
+    if entry.accepted.as_deref() == Some(answer) {
+      return false;
+    }
+    entry.rejected.retain(|r| r.answer != answer);
+    entry.rejected.push(Rejected {
+      answer: answer.to_string(),
+      hint,
+    });
+    true
+  }
+
+  fn entry(&self, part: Part) -> &Entry {
+    match part {
+      Part::One => &self.part_1,
+      Part::Two => &self.part_2,
+    }
+  }
+
+  fn entry_mut(&mut self, part: Part) -> &mut Entry {
+    match part {
+      Part::One => &mut self.part_1,
+      Part::Two => &mut self.part_2,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Hint, Ledger, Part, Verdict};
+
+  #[test]
+  fn checks_recorded_answer() {
+    let mut ledger = Ledger::default();
+    assert_eq!(ledger.check(Part::One, "42"), None);
+    ledger.record(Part::One, "42");
+    assert_eq!(ledger.check(Part::One, "42"), Some(Verdict::Matches));
+    assert_eq!(
+      ledger.check(Part::One, "43"),
+      Some(Verdict::Mismatch("42".to_string()))
+    );
+    assert_eq!(ledger.check(Part::Two, "42"), None);
+  }
+
+  #[test]
+  fn checks_rejected_hints() {
+    let mut ledger = Ledger::default();
+    assert!(ledger.reject(Part::Two, "100", Some(Hint::TooHigh)));
+    assert!(ledger.reject(Part::Two, "10", Some(Hint::TooLow)));
+    assert_eq!(
+      ledger.check(Part::Two, "100"),
+      Some(Verdict::Rejected(Some(Hint::TooHigh)))
+    );
+    assert_eq!(
+      ledger.check(Part::Two, "150"),
+      Some(Verdict::Beyond(Hint::TooHigh, "100".to_string()))
+    );
+    assert_eq!(
+      ledger.check(Part::Two, "5"),
+      Some(Verdict::Beyond(Hint::TooLow, "10".to_string()))
+    );
+    assert_eq!(ledger.check(Part::Two, "50"), None);
+
+    ledger.record(Part::Two, "50");
+    assert!(!ledger.reject(Part::Two, "50", None));
+  }
+}
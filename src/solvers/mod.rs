@@ -1,74 +1,177 @@
-use std::{fmt::Display, fs::File, time::Instant};
-
-pub mod day1;
-pub mod day10;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+use std::{
+  fmt::Display,
+  io::{self, BufRead},
+};
+
+pub use example::{Example, ExampleInput, ExampleResult};
+pub use ledger::{Hint, Ledger};
+pub use params::{Param, Params};
+pub use parse::{Line, ParseError};
+pub use progress::Progress;
+pub use registry::{DynSolver, Part, Registered};
+pub use runner::{DayReport, ExampleMode, RunOptions};
+
+pub mod alloc;
+pub mod example;
+pub mod ledger;
+pub mod params;
+pub mod parse;
+pub mod progress;
+pub mod registry;
+pub mod runner;
+pub mod style;
+#[cfg(test)]
+mod tests;
+
+/// Declares the day modules, lists their solvers in `registry()` and generates
+/// example and pinned answer tests for each. Adding a day only takes a new line here.
+macro_rules! solvers {
+  ($($day:ident::$solver:ident),* $(,)?) => {
+    $(pub mod $day;)*
+
+    pub fn registry() -> Vec<Box<dyn DynSolver>> {
+      vec![$(Box::new(Registered::<$day::$solver>::default())),*]
+    }
+
+    #[cfg(test)]
+    mod registry_tests {
+      $(
+        mod $day {
+          use crate::solvers::{Part, Registered, tests};
+
+          fn solver() -> Registered<crate::solvers::$day::$solver> {
+            Registered::default()
+          }
+
+          #[test]
+          fn part_1_inline_examples() {
+            tests::check_examples(&solver(), Part::One, false);
+          }
+
+          #[test]
+          fn part_2_inline_examples() {
+            tests::check_examples(&solver(), Part::Two, false);
+          }
+
+          #[test]
+          #[ignore = "reads example files from input/"]
+          fn part_1_file_examples() {
+            tests::check_examples(&solver(), Part::One, true);
+          }
+
+          #[test]
+          #[ignore = "reads example files from input/"]
+          fn part_2_file_examples() {
+            tests::check_examples(&solver(), Part::Two, true);
+          }
+
+          #[test]
+          #[ignore = "reads the puzzle input from input/"]
+          fn part_1_answer() {
+            tests::check_answer(&solver(), Part::One);
+          }
+
+          #[test]
+          #[ignore = "reads the puzzle input from input/"]
+          fn part_2_answer() {
+            tests::check_answer(&solver(), Part::Two);
+          }
+        }
+      )*
+    }
+  };
+}
+
+solvers! {
+  day1::Day1,
+  day2::Day2,
+  day3::Day3,
+  day4::Day4,
+  day5::Day5,
+  day6::Day6,
+  day7::Day7,
+  day8::Day8,
+  day9::Day9,
+  day10::Day10,
+}
+
+pub fn find(day: u8) -> Option<Box<dyn DynSolver>> {
+  registry().into_iter().find(|solver| solver.day() == day)
+}
 
 pub trait Solver {
+  const DAY: u8;
+  const TITLE: &'static str;
+  /// Puzzle constants the parts read from `Params` instead of hard-coding
+  const PARAMS: &'static [Param] = &[];
+
   type ParsedInput;
   type Output1: Display;
   type Output2: Display;
 
-  fn parse(input: &mut File) -> Self::ParsedInput;
+  fn parse(input: &str) -> Result<Self::ParsedInput, ParseError>;
 
   fn part_1_test_output() -> Option<Self::Output1>;
+  /// Examples checked in addition to `part_1_test.txt`
+  fn part_1_examples() -> Vec<Example<Self::Output1>> {
+    Vec::new()
+  }
   fn part_1(parsed_input: &Self::ParsedInput) -> Self::Output1;
-  fn part_1_flag(parsed_input: &Self::ParsedInput, _is_test: bool) -> Self::Output1 {
+  /// Like `part_1`, with the puzzle parameters and a handle to report progress through
+  fn part_1_with(
+    parsed_input: &Self::ParsedInput,
+    _params: &Params,
+    _progress: &Progress,
+  ) -> Self::Output1 {
     Self::part_1(parsed_input)
   }
 
   fn part_2_test_output() -> Option<Self::Output2>;
+  /// Examples checked in addition to `part_2_test.txt`
+  fn part_2_examples() -> Vec<Example<Self::Output2>> {
+    Vec::new()
+  }
   fn part_2(parsed_input: &Self::ParsedInput) -> Self::Output2;
-  fn part_2_flag(parsed_input: &Self::ParsedInput, _is_test: bool) -> Self::Output2 {
+  fn part_2_with(
+    parsed_input: &Self::ParsedInput,
+    _params: &Params,
+    _progress: &Progress,
+  ) -> Self::Output2 {
     Self::part_2(parsed_input)
   }
 
-  fn parse_file(path: &str) -> Self::ParsedInput {
-    match File::open(path) {
-      Ok(mut file) => Self::parse(&mut file),
-      Err(error) => panic!("Error opening input file: {}", error),
-    }
+  /// Accepted answers for the real input, checked by `cargo test` once pinned
+  fn part_1_answer() -> Option<Self::Output1> {
+    None
+  }
+  fn part_2_answer() -> Option<Self::Output2> {
+    None
   }
 
-  fn test(day_path: &str) {
-    let part_1_expected = Self::part_1_test_output();
-    if let Some(part_1_expected) = part_1_expected {
-      let part_1_input = Self::parse_file(&format!("{}/part_1_test.txt", day_path));
-      assert_eq!(
-        Self::part_1_flag(&part_1_input, true).to_string(),
-        part_1_expected.to_string()
-      );
-    }
-
-    let part_2_expected = Self::part_2_test_output();
-    if let Some(part_2_expected) = part_2_expected {
-      let part_2_input = Self::parse_file(&format!("{}/part_2_test.txt", day_path));
-      assert_eq!(
-        Self::part_2_flag(&part_2_input, true).to_string(),
-        part_2_expected.to_string()
-      );
-    }
+  fn parse_str(input: &str) -> Result<Self::ParsedInput, ParseError> {
+    Self::parse(input)
   }
 
-  fn run(day_path: &str) {
-    Self::test(day_path);
+  fn parse_reader<R: BufRead>(mut reader: R) -> Result<Self::ParsedInput, ParseError> {
+    let mut str = String::new();
+    reader
+      .read_to_string(&mut str)
+      .map_err(|error| ParseError::Io {
+        day: Self::DAY,
+        error,
+      })?;
+    Self::parse_str(&str)
+  }
 
-    let path = format!("{}/input.txt", day_path);
-    let input = Self::parse_file(&path);
+  fn parse_file(path: &str) -> Result<Self::ParsedInput, ParseError> {
+    Self::parse_str(&parse::read_file(Self::DAY, path)?)
+  }
 
-    let before_1 = Instant::now();
-    println!("Part 1: {}", Self::part_1_flag(&input, false));
-    println!("Part 1 took {:?}", before_1.elapsed());
+  fn parse_stdin() -> Result<Self::ParsedInput, ParseError> {
+    Self::parse_reader(io::stdin().lock())
+  }
 
-    let before_2 = Instant::now();
-    println!("Part 2: {}", Self::part_2_flag(&input, false));
-    println!("Part 2 took {:?}", before_2.elapsed());
+  fn line(index: usize, text: &str) -> Line<'_> {
+    Line::new(Self::DAY, index, text)
   }
 }
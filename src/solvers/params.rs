@@ -0,0 +1,102 @@
+use std::fmt;
+
+/// A named puzzle constant, e.g. how many connections Day8 makes, which differs
+/// between the examples and the real input.
+pub struct Param {
+  pub name: &'static str,
+  pub example: i64,
+  pub real: i64,
+  /// Values the day can handle, overrides outside it are rejected before solving
+  pub min: i64,
+  pub max: i64,
+}
+
+impl Param {
+  pub const fn new(name: &'static str, example: i64, real: i64) -> Self {
+    Self {
+      name,
+      example,
+      real,
+      min: i64::MIN,
+      max: i64::MAX,
+    }
+  }
+
+  pub const fn range(self, min: i64, max: i64) -> Self {
+    Self { min, max, ..self }
+  }
+
+  pub fn check(&self, value: i64) -> Result<(), String> {
+    if (self.min..=self.max).contains(&value) {
+      Ok(())
+    } else {
+      Err(format!(
+        "Parameter {} must be between {} and {}, got {}",
+        self.name, self.min, self.max, value
+      ))
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Params(Vec<(&'static str, i64)>);
+
+impl Params {
+  pub fn example(declared: &[Param]) -> Self {
+    Self(declared.iter().map(|p| (p.name, p.example)).collect())
+  }
+
+  pub fn real(declared: &[Param]) -> Self {
+    Self(declared.iter().map(|p| (p.name, p.real)).collect())
+  }
+
+  /// Overrides a declared parameter, returning false if there is none by that name.
+  pub fn set(&mut self, name: &str, value: i64) -> bool {
+    match self.0.iter_mut().find(|(n, _)| *n == name) {
+      Some((_, v)) => {
+        *v = value;
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Panics if the parameter was not declared or does not fit in `T`, declare a range
+  /// that fits so overrides are rejected up front.
+  pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+    let value = self
+      .0
+      .iter()
+      .find(|(n, _)| *n == name)
+      .unwrap_or_else(|| panic!("Parameter {} not declared", name))
+      .1;
+    T::try_from(value).unwrap_or_else(|_| panic!("Parameter {} is out of range: {}", name, value))
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl fmt::Display for Params {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let pairs: Vec<String> = self.0.iter().map(|(n, v)| format!("{}={}", n, v)).collect();
+    write!(f, "{}", pairs.join(", "))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Param;
+
+  #[test]
+  fn checks_overrides_against_the_range() {
+    let param = Param::new("start", 50, 50).range(0, 99);
+    assert!(param.check(0).is_ok() && param.check(99).is_ok());
+    assert_eq!(
+      param.check(-5),
+      Err("Parameter start must be between 0 and 99, got -5".to_string())
+    );
+    assert!(Param::new("any", 0, 0).check(i64::MIN).is_ok());
+  }
+}
@@ -0,0 +1,135 @@
+use std::{
+  error::Error,
+  fmt, fs,
+  io::{self, Read},
+  str::FromStr,
+};
+
+#[derive(Debug)]
+pub enum ParseError {
+  Io {
+    day: u8,
+    error: io::Error,
+  },
+  Invalid {
+    day: u8,
+    line: usize,
+    column: usize,
+    text: String,
+    reason: String,
+  },
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseError::Io { day, error } => write!(f, "Day {}: could not read input: {}", day, error),
+      ParseError::Invalid {
+        day,
+        line,
+        column,
+        text,
+        reason,
+      } => write!(
+        f,
+        "Day {}, line {}, column {}: {} (found {:?})",
+        day, line, column, reason, text
+      ),
+    }
+  }
+}
+
+impl Error for ParseError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ParseError::Io { error, .. } => Some(error),
+      ParseError::Invalid { .. } => None,
+    }
+  }
+}
+
+pub fn read_file(day: u8, path: &str) -> Result<String, ParseError> {
+  fs::read_to_string(path).map_err(|error| ParseError::Io {
+    day,
+    error: io::Error::new(error.kind(), format!("{}: {}", path, error)),
+  })
+}
+
+/// Like [`read_file`], but reads stdin if `path` is `-`.
+pub fn read_input(day: u8, path: &str) -> Result<String, ParseError> {
+  if path != "-" {
+    return read_file(day, path);
+  }
+
+  let mut str = String::new();
+  io::stdin()
+    .read_to_string(&mut str)
+    .map_err(|error| ParseError::Io { day, error })?;
+  Ok(str)
+}
+
+/// A single input line, used to point parse errors at the offending text.
+/// Line and column numbers are 1-based.
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+  pub day: u8,
+  pub number: usize,
+  pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+  pub fn new(day: u8, index: usize, text: &'a str) -> Self {
+    Self {
+      day,
+      number: index + 1,
+      text,
+    }
+  }
+
+  /// Error pointing at `part`, which must be a subslice of this line.
+  pub fn error(&self, part: &str, reason: &str) -> ParseError {
+    ParseError::Invalid {
+      day: self.day,
+      line: self.number,
+      column: self.column_of(part),
+      text: part.to_string(),
+      reason: reason.to_string(),
+    }
+  }
+
+  /// Error pointing at a 1-based character column.
+  pub fn error_at(&self, column: usize, reason: &str) -> ParseError {
+    ParseError::Invalid {
+      day: self.day,
+      line: self.number,
+      column,
+      text: self.text.chars().skip(column - 1).take(1).collect(),
+      reason: reason.to_string(),
+    }
+  }
+
+  /// Error for a field that is missing at the end of the line.
+  pub fn missing(&self, reason: &str) -> ParseError {
+    self.error(&self.text[self.text.len()..], reason)
+  }
+
+  pub fn parse<T: FromStr>(&self, part: &str, reason: &str) -> Result<T, ParseError> {
+    part.parse().map_err(|_| self.error(part, reason))
+  }
+
+  /// Parses an optional field, as produced by `split(..).next()`.
+  pub fn parse_next<T: FromStr>(&self, part: Option<&str>, reason: &str) -> Result<T, ParseError> {
+    match part {
+      Some(part) => self.parse(part, reason),
+      None => Err(self.missing(reason)),
+    }
+  }
+
+  fn column_of(&self, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize)
+      .checked_sub(self.text.as_ptr() as usize)
+      .filter(|offset| *offset <= self.text.len())
+      .unwrap_or(0);
+    self.text[..offset].chars().count() + 1
+  }
+}
@@ -0,0 +1,121 @@
+use std::{
+  io::{self, IsTerminal, Write},
+  sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+  },
+  time::{Duration, Instant},
+};
+
+const REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Lets a part report how far along it is, and tells it when to give up. Reporting
+/// through a disabled handle does nothing, so parts can report from their hot loops.
+#[derive(Clone, Default)]
+pub struct Progress {
+  line: Option<Arc<Line>>,
+  /// Shared by all clones, set when the part timed out
+  cancelled: Arc<AtomicBool>,
+}
+
+struct Line {
+  prefix: String,
+  state: Mutex<State>,
+  /// Set once the part is done or abandoned, a timed out part may keep reporting
+  finished: AtomicBool,
+}
+
+#[derive(Default)]
+struct State {
+  label: String,
+  last_draw: Option<Instant>,
+}
+
+impl Progress {
+  pub fn disabled() -> Self {
+    Self::default()
+  }
+
+  /// A single updating line on stderr, disabled if stderr is not a terminal.
+  pub fn terminal(prefix: String) -> Self {
+    if !io::stderr().is_terminal() {
+      return Self::disabled();
+    }
+    Self {
+      line: Some(Arc::new(Line {
+        prefix,
+        state: Mutex::new(State::default()),
+        finished: AtomicBool::new(false),
+      })),
+      cancelled: Arc::default(),
+    }
+  }
+
+  /// Shown after the count, e.g. what is being counted.
+  pub fn set_label(&self, label: &str) {
+    if let Some(line) = &self.line {
+      line.state.lock().unwrap().label = label.to_string();
+    }
+  }
+
+  pub fn set(&self, current: usize, total: usize) {
+    if let Some(line) = &self.line {
+      line.draw(current, total);
+    }
+  }
+
+  /// Asks the part to stop, its answer is discarded.
+  pub fn cancel(&self) {
+    self.cancelled.store(true, Ordering::Relaxed);
+  }
+
+  /// Long-running parts should check this in their loops and return early once it is
+  /// set, with any answer. Parts that never check keep running until they finish.
+  pub fn is_cancelled(&self) -> bool {
+    self.cancelled.load(Ordering::Relaxed)
+  }
+
+  /// Clears the line, so the answer can be printed in its place.
+  pub fn finish(&self) {
+    if let Some(line) = &self.line {
+      line.finished.store(true, Ordering::Relaxed);
+      if line.state.lock().unwrap().last_draw.is_some() {
+        eprint!("\r\x1b[2K");
+      }
+    }
+  }
+}
+
+impl Line {
+  fn draw(&self, current: usize, total: usize) {
+    if self.finished.load(Ordering::Relaxed) {
+      return;
+    }
+    // Another thread is drawing, this update would be throttled anyway
+    let Ok(mut state) = self.state.try_lock() else {
+      return;
+    };
+    if state
+      .last_draw
+      .is_some_and(|last| last.elapsed() < REDRAW_INTERVAL)
+    {
+      return;
+    }
+    state.last_draw = Some(Instant::now());
+
+    // Wide enough that counts near usize::MAX cannot overflow
+    let percent = (current as u128 * 100)
+      .checked_div(total as u128)
+      .unwrap_or(0);
+    let label = if state.label.is_empty() {
+      String::new()
+    } else {
+      format!(" {}", state.label)
+    };
+    eprint!(
+      "\r\x1b[2K{} {}/{}{} ({}%)",
+      self.prefix, current, total, label, percent
+    );
+    let _ = io::stderr().flush();
+  }
+}
@@ -0,0 +1,153 @@
+use std::{any::Any, fmt, marker::PhantomData};
+
+use super::{Example, Param, Params, ParseError, Progress, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+  pub fn number(self) -> u8 {
+    match self {
+      Part::One => 1,
+      Part::Two => 2,
+    }
+  }
+
+  pub fn from_number(number: u8) -> Option<Part> {
+    Part::ALL.into_iter().find(|part| part.number() == number)
+  }
+
+  /// Both parts, or only the given one.
+  pub fn select(only: Option<Part>) -> Vec<Part> {
+    Part::ALL
+      .into_iter()
+      .filter(|part| only.is_none_or(|only| only == *part))
+      .collect()
+  }
+}
+
+impl fmt::Display for Part {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.number())
+  }
+}
+
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/// Object-safe view of a `Solver`, so days can be stored and iterated together.
+pub trait DynSolver: Send + Sync {
+  fn day(&self) -> u8;
+  fn title(&self) -> &'static str;
+  fn params(&self) -> &'static [Param];
+
+  fn parse(&self, input: &str) -> Result<ParsedInput, ParseError>;
+  fn parse_file(&self, path: &str) -> Result<ParsedInput, ParseError>;
+  /// Panics if `parsed_input` was not produced by this solver's `parse`.
+  fn solve(
+    &self,
+    part: Part,
+    parsed_input: &ParsedInput,
+    params: &Params,
+    progress: &Progress,
+  ) -> String;
+  fn examples(&self, part: Part) -> Vec<Example<String>>;
+  fn answer(&self, part: Part) -> Option<String>;
+  /// An owned handle to the same solver, for solving on another thread.
+  fn boxed(&self) -> Box<dyn DynSolver>;
+
+  fn run_part(&self, part: Part, input: &str) -> Result<String, ParseError> {
+    let parsed_input = self.parse(input)?;
+    Ok(self.solve(
+      part,
+      &parsed_input,
+      &Params::real(self.params()),
+      &Progress::disabled(),
+    ))
+  }
+}
+
+pub struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S> Default for Registered<S> {
+  fn default() -> Self {
+    Self(PhantomData)
+  }
+}
+
+impl<S> DynSolver for Registered<S>
+where
+  S: Solver + 'static,
+  S::ParsedInput: Send + Sync + 'static,
+{
+  fn day(&self) -> u8 {
+    S::DAY
+  }
+
+  fn title(&self) -> &'static str {
+    S::TITLE
+  }
+
+  fn params(&self) -> &'static [Param] {
+    S::PARAMS
+  }
+
+  fn parse(&self, input: &str) -> Result<ParsedInput, ParseError> {
+    Ok(Box::new(S::parse(input)?))
+  }
+
+  fn parse_file(&self, path: &str) -> Result<ParsedInput, ParseError> {
+    Ok(Box::new(S::parse_file(path)?))
+  }
+
+  fn solve(
+    &self,
+    part: Part,
+    parsed_input: &ParsedInput,
+    params: &Params,
+    progress: &Progress,
+  ) -> String {
+    let parsed_input = parsed_input
+      .downcast_ref::<S::ParsedInput>()
+      .expect("Parsed input belongs to another day");
+    match part {
+      Part::One => S::part_1_with(parsed_input, params, progress).to_string(),
+      Part::Two => S::part_2_with(parsed_input, params, progress).to_string(),
+    }
+  }
+
+  fn examples(&self, part: Part) -> Vec<Example<String>> {
+    fn stringify<T: ToString>(
+      test_file: &str,
+      test_output: Option<T>,
+      examples: Vec<Example<T>>,
+    ) -> Vec<Example<String>> {
+      test_output
+        .map(|expected| Example::file(test_file, expected))
+        .into_iter()
+        .chain(examples)
+        .map(|example| example.map(|expected| expected.to_string()))
+        .collect()
+    }
+
+    let test_file = format!("part_{}_test.txt", part);
+    match part {
+      Part::One => stringify(&test_file, S::part_1_test_output(), S::part_1_examples()),
+      Part::Two => stringify(&test_file, S::part_2_test_output(), S::part_2_examples()),
+    }
+  }
+
+  fn answer(&self, part: Part) -> Option<String> {
+    match part {
+      Part::One => S::part_1_answer().map(|o| o.to_string()),
+      Part::Two => S::part_2_answer().map(|o| o.to_string()),
+    }
+  }
+  fn boxed(&self) -> Box<dyn DynSolver> {
+    Box::new(Self::default())
+  }
+}
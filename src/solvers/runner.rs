@@ -0,0 +1,470 @@
+use std::{
+  hint, panic,
+  sync::{Arc, mpsc},
+  thread,
+  time::{Duration, Instant},
+};
+
+use super::{
+  DynSolver, ExampleInput, ExampleResult, Params, ParseError, Part, Progress,
+  alloc::{self, AllocStats},
+  ledger::{Ledger, Verdict},
+  parse,
+  registry::ParsedInput,
+  style,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleMode {
+  /// Check the examples and stop before the real input if any fail
+  Check,
+  /// Check the examples but solve the real input regardless
+  Continue,
+  Skip,
+}
+
+pub struct RunOptions {
+  /// With `repeat` above 1 each part is solved again on the same parsed input,
+  /// so the reported time is not dominated by first-run warmup.
+  pub repeat: u32,
+  pub examples: ExampleMode,
+  /// Overrides for the real input, parameters a day does not declare are ignored
+  pub params: Vec<(String, i64)>,
+  /// Real input path instead of `input.txt` in the day directory, `-` for stdin
+  pub input: Option<String>,
+  /// Record the answers as accepted in the ledger
+  pub record: bool,
+  /// Only check and solve this part, both if None
+  pub part: Option<Part>,
+  /// Give up on a part that takes longer, and move on
+  pub timeout: Option<Duration>,
+}
+
+impl Default for RunOptions {
+  fn default() -> Self {
+    Self {
+      repeat: 1,
+      examples: ExampleMode::Check,
+      params: Vec::new(),
+      input: None,
+      record: false,
+      part: None,
+      timeout: None,
+    }
+  }
+}
+
+pub struct PartReport {
+  pub part: Part,
+  /// None if the part timed out
+  pub answer: Option<String>,
+  pub duration: Duration,
+  /// Average over the runs after the first, None unless `repeat` is above 1
+  pub repeat_average: Option<Duration>,
+  pub alloc: Option<AllocStats>,
+  /// Empty if the part has no examples or they were skipped
+  pub examples: Vec<ExampleResult>,
+  /// None without a ledger or if it knows nothing about the answer
+  pub verdict: Option<Verdict>,
+}
+
+pub struct DayReport {
+  pub day: u8,
+  pub title: &'static str,
+  pub input_path: String,
+  pub parse_duration: Duration,
+  pub parse_alloc: Option<AllocStats>,
+  pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+  pub fn total_duration(&self) -> Duration {
+    self.parse_duration + self.parts.iter().map(|p| p.duration).sum::<Duration>()
+  }
+
+  pub fn examples_passed(&self) -> bool {
+    self
+      .parts
+      .iter()
+      .flat_map(|p| &p.examples)
+      .all(|e| e.passed())
+  }
+
+  /// All examples passed and no part timed out.
+  pub fn passed(&self) -> bool {
+    self.examples_passed() && self.parts.iter().all(|p| p.answer.is_some())
+  }
+}
+
+pub fn check_part_examples(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  part: Part,
+) -> Result<Vec<ExampleResult>, ParseError> {
+  solver
+    .examples(part)
+    .into_iter()
+    .map(|example| {
+      let input = match &example.input {
+        ExampleInput::File(name) => solver.parse_file(&format!("{}/{}", day_path, name))?,
+        ExampleInput::Inline(text) => solver.parse(text)?,
+      };
+      Ok(ExampleResult {
+        part,
+        actual: solver.solve(
+          part,
+          &input,
+          &Params::example(solver.params()),
+          &Progress::disabled(),
+        ),
+        input: example.input,
+        expected: example.expected,
+      })
+    })
+    .collect()
+}
+
+fn check_examples(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  parts: &[Part],
+  mode: ExampleMode,
+) -> Result<Vec<Vec<ExampleResult>>, ParseError> {
+  parts
+    .iter()
+    .map(|&part| match mode {
+      ExampleMode::Skip => Ok(Vec::new()),
+      ExampleMode::Check | ExampleMode::Continue => check_part_examples(solver, day_path, part),
+    })
+    .collect()
+}
+
+/// Compares before recording, so a recorded mismatch is still reported once.
+fn check_ledger(
+  ledger: Option<&mut Ledger>,
+  part: Part,
+  answer: &str,
+  record: bool,
+) -> Option<Verdict> {
+  let ledger = ledger?;
+  let verdict = ledger.check(part, answer);
+  if record {
+    ledger.record(part, answer);
+  }
+  verdict
+}
+
+/// How long a cancelled part gets to stop before the next one starts
+const CANCEL_GRACE: Duration = Duration::from_millis(200);
+
+/// Returns None if the part takes longer than the timeout.
+fn solve(
+  solver: &dyn DynSolver,
+  part: Part,
+  parsed_input: &Arc<ParsedInput>,
+  params: &Params,
+  progress: &Progress,
+  timeout: Option<Duration>,
+) -> Option<String> {
+  let Some(timeout) = timeout else {
+    return Some(solver.solve(part, parsed_input, params, progress));
+  };
+
+  let solver = solver.boxed();
+  let parsed_input = Arc::clone(parsed_input);
+  let params = params.clone();
+  with_timeout(timeout, progress, move |progress| {
+    solver.solve(part, &parsed_input, &params, progress)
+  })
+}
+
+/// Solves the part `repeat - 1` more times and returns the average time. The runs share
+/// a budget of one timeout each, None if they exceed it.
+fn repeat_average(
+  solver: &dyn DynSolver,
+  part: Part,
+  parsed_input: &Arc<ParsedInput>,
+  params: &Params,
+  repeat: u32,
+  timeout: Option<Duration>,
+) -> Option<Duration> {
+  let runs = repeat - 1;
+  let solver = solver.boxed();
+  let parsed_input = Arc::clone(parsed_input);
+  let params = params.clone();
+  let run_all = move |progress: &Progress| {
+    let before = Instant::now();
+    for _ in 0..runs {
+      hint::black_box(solver.solve(part, &parsed_input, &params, progress));
+    }
+    before.elapsed() / runs
+  };
+  match timeout {
+    Some(timeout) => with_timeout(
+      timeout.checked_mul(runs).unwrap_or(Duration::MAX),
+      &Progress::disabled(),
+      run_all,
+    ),
+    None => Some(run_all(&Progress::disabled())),
+  }
+}
+
+/// Runs `f` on another thread, None if it takes longer than the timeout. The progress
+/// handle is cancelled then, Rust threads cannot be stopped, so a part that does not
+/// check it keeps running in the background until the process exits.
+fn with_timeout<T: Send + 'static>(
+  timeout: Duration,
+  progress: &Progress,
+  f: impl FnOnce(&Progress) -> T + Send + 'static,
+) -> Option<T> {
+  let (sender, receiver) = mpsc::channel();
+  let thread_progress = progress.clone();
+  let handle = thread::spawn(move || {
+    // The receiver is gone if the part already timed out
+    let _ = sender.send(f(&thread_progress));
+  });
+  match receiver.recv_timeout(timeout) {
+    Ok(value) => Some(value),
+    Err(mpsc::RecvTimeoutError::Timeout) => {
+      progress.cancel();
+      // Wait for a part that checks the flag, so it does not compete with the next one
+      if !matches!(
+        receiver.recv_timeout(CANCEL_GRACE),
+        Err(mpsc::RecvTimeoutError::Timeout)
+      ) {
+        let _ = handle.join();
+      }
+      None
+    }
+    Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
+      Err(panic) => panic::resume_unwind(panic),
+      Ok(()) => unreachable!("Solver thread finished without an answer"),
+    },
+  }
+}
+
+fn alloc_note(stats: Option<AllocStats>) -> String {
+  stats.map(|s| format!(", {}", s)).unwrap_or_default()
+}
+
+fn timed_out(timeout: Option<Duration>) -> String {
+  style::red(&format!(
+    "timed out after {:?}",
+    timeout.unwrap_or_default()
+  ))
+}
+
+/// The real input path, `input.txt` in the day directory unless overridden.
+pub fn input_path(day_path: &str, input: Option<&str>) -> String {
+  match input {
+    Some(path) => path.to_string(),
+    None => format!("{}/input.txt", day_path),
+  }
+}
+
+pub fn real_params(solver: &dyn DynSolver, overrides: &[(String, i64)]) -> Params {
+  let mut params = Params::real(solver.params());
+  for (name, value) in overrides {
+    params.set(name, *value);
+  }
+  params
+}
+
+fn print_examples(examples: &[Vec<ExampleResult>]) -> bool {
+  for example in examples.iter().flatten() {
+    example.print();
+  }
+  examples.iter().flatten().all(|e| e.passed())
+}
+
+/// Only checks the examples, returns false if any failed.
+pub fn test(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  part: Option<Part>,
+) -> Result<bool, ParseError> {
+  println!("Day {}: {}", solver.day(), solver.title());
+  let examples = check_examples(solver, day_path, &Part::select(part), ExampleMode::Check)?;
+  Ok(print_examples(&examples))
+}
+
+/// Returns false if an example failed or a part timed out. Answers are compared against the ledger if
+/// given, and recorded in it with `options.record`.
+pub fn run(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  options: &RunOptions,
+  mut ledger: Option<&mut Ledger>,
+) -> Result<bool, ParseError> {
+  println!("Day {}: {}", solver.day(), solver.title());
+
+  let parts = Part::select(options.part);
+  let examples = check_examples(solver, day_path, &parts, options.examples)?;
+  let examples_passed = print_examples(&examples);
+  if !examples_passed && options.examples == ExampleMode::Check {
+    println!("Skipping the real input, pass --continue to solve it anyway");
+    return Ok(false);
+  }
+
+  let input_path = input_path(day_path, options.input.as_deref());
+  let input = parse::read_input(solver.day(), &input_path)?;
+  let before_parse = Instant::now();
+  let (parsed_input, parse_alloc) = alloc::track(|| solver.parse(&input));
+  let parsed_input = Arc::new(parsed_input?);
+  println!(
+    "Parsing took {:?}{}",
+    before_parse.elapsed(),
+    alloc_note(parse_alloc)
+  );
+
+  let params = real_params(solver, &options.params);
+  if !params.is_empty() {
+    println!("Parameters: {}", params);
+  }
+
+  let repeat = options.repeat;
+  let mut passed = examples_passed;
+  for part in parts {
+    let progress = Progress::terminal(format!("Part {}:", part));
+    let before = Instant::now();
+    let (answer, part_alloc) = alloc::track(|| {
+      solve(
+        solver,
+        part,
+        &parsed_input,
+        &params,
+        &progress,
+        options.timeout,
+      )
+    });
+    progress.finish();
+    let Some(answer) = answer else {
+      println!("Part {}: {}", part, timed_out(options.timeout));
+      passed = false;
+      continue;
+    };
+    let first = before.elapsed();
+    let mut notes = String::new();
+    // Pinned answers belong to input.txt, another input is expected to differ
+    let pinned = solver.answer(part).filter(|_| options.input.is_none());
+    if let Some(pinned) = pinned.filter(|pinned| *pinned != answer) {
+      notes += &format!(" {}", style::red(&format!("(pinned answer is {})", pinned)));
+    }
+    if let Some(verdict) = check_ledger(ledger.as_deref_mut(), part, &answer, options.record) {
+      let note = format!("({})", verdict);
+      if verdict.is_match() {
+        notes += &format!(" {}", style::green(&note));
+      } else {
+        notes += &format!(" {}", style::red(&note));
+      }
+    }
+    println!("Part {}: {}{}", part, answer, notes);
+
+    if repeat > 1 {
+      let Some(average) = repeat_average(
+        solver,
+        part,
+        &parsed_input,
+        &params,
+        repeat,
+        options.timeout,
+      ) else {
+        println!(
+          "Part {} took {:?} (first run), the {} more runs {}",
+          part,
+          first,
+          repeat - 1,
+          timed_out(options.timeout)
+        );
+        passed = false;
+        continue;
+      };
+      println!(
+        "Part {} took {:?} (first run), {:?} on average over {} more runs{}",
+        part,
+        first,
+        average,
+        repeat - 1,
+        alloc_note(part_alloc)
+      );
+    } else {
+      println!("Part {} took {:?}{}", part, first, alloc_note(part_alloc));
+    }
+  }
+
+  Ok(passed)
+}
+
+/// Checks the examples, then parses and solves the real input without printing.
+/// The real input is solved even if an example fails, the report records it.
+pub fn measure(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  options: &RunOptions,
+  mut ledger: Option<&mut Ledger>,
+) -> Result<DayReport, ParseError> {
+  let parts = Part::select(options.part);
+  let examples = check_examples(solver, day_path, &parts, options.examples)?;
+  let params = real_params(solver, &options.params);
+
+  let input_path = input_path(day_path, options.input.as_deref());
+  let input = parse::read_input(solver.day(), &input_path)?;
+  let before_parse = Instant::now();
+  let (parsed_input, parse_alloc) = alloc::track(|| solver.parse(&input));
+  let parsed_input = Arc::new(parsed_input?);
+  let parse_duration = before_parse.elapsed();
+
+  let parts = parts
+    .into_iter()
+    .zip(examples)
+    .map(|(part, examples)| {
+      let before = Instant::now();
+      let (mut answer, alloc) = alloc::track(|| {
+        solve(
+          solver,
+          part,
+          &parsed_input,
+          &params,
+          &Progress::disabled(),
+          options.timeout,
+        )
+      });
+      let duration = before.elapsed();
+      let mut repeat_average = None;
+      if answer.is_some() && options.repeat > 1 {
+        repeat_average = self::repeat_average(
+          solver,
+          part,
+          &parsed_input,
+          &params,
+          options.repeat,
+          options.timeout,
+        );
+        // Repeats over the timeout count as the part timing out
+        if repeat_average.is_none() {
+          answer = None;
+        }
+      }
+      PartReport {
+        part,
+        repeat_average,
+        verdict: answer
+          .as_ref()
+          .and_then(|answer| check_ledger(ledger.as_deref_mut(), part, answer, options.record)),
+        answer,
+        duration,
+        alloc,
+        examples,
+      }
+    })
+    .collect();
+
+  Ok(DayReport {
+    day: solver.day(),
+    title: solver.title(),
+    input_path,
+    parse_duration,
+    parse_alloc,
+    parts,
+  })
+}
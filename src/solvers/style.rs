@@ -0,0 +1,17 @@
+use std::io::{self, IsTerminal};
+
+fn paint(text: &str, code: &str) -> String {
+  if io::stdout().is_terminal() {
+    format!("\x1b[{}m{}\x1b[0m", code, text)
+  } else {
+    text.to_string()
+  }
+}
+
+pub fn red(text: &str) -> String {
+  paint(text, "31")
+}
+
+pub fn green(text: &str) -> String {
+  paint(text, "32")
+}
@@ -0,0 +1,54 @@
+use super::{DynSolver, ExampleInput, Params, Part, Progress, parse};
+
+// Puzzle inputs are personal and not committed, so the tests reading them are ignored
+// by default and run with `cargo test -- --ignored` where the files exist
+fn day_path(day: u8) -> String {
+  format!("{}/input/day{}", env!("CARGO_MANIFEST_DIR"), day)
+}
+
+/// Checks the inline examples, or only the file ones. A missing file fails the test.
+pub fn check_examples(solver: &dyn DynSolver, part: Part, files: bool) {
+  let day_path = day_path(solver.day());
+  for example in solver.examples(part) {
+    let input = match (&example.input, files) {
+      (ExampleInput::File(name), true) => {
+        let path = format!("{}/{}", day_path, name);
+        parse::read_file(solver.day(), &path).unwrap_or_else(|e| panic!("{}", e))
+      }
+      (ExampleInput::Inline(text), false) => text.to_string(),
+      _ => continue,
+    };
+    let parsed_input = solver.parse(&input).unwrap_or_else(|e| panic!("{}", e));
+    assert_eq!(
+      solver.solve(
+        part,
+        &parsed_input,
+        &Params::example(solver.params()),
+        &Progress::disabled()
+      ),
+      example.expected,
+      "Day {} part {} example {}",
+      solver.day(),
+      part,
+      example.input
+    );
+  }
+}
+
+pub fn check_answer(solver: &dyn DynSolver, part: Part) {
+  let Some(expected) = solver.answer(part) else {
+    return;
+  };
+  let path = format!("{}/input.txt", day_path(solver.day()));
+  let input = parse::read_file(solver.day(), &path).unwrap_or_else(|e| panic!("{}", e));
+  let actual = solver
+    .run_part(part, &input)
+    .unwrap_or_else(|e| panic!("{}", e));
+  assert_eq!(
+    actual,
+    expected,
+    "Day {} part {} answer",
+    solver.day(),
+    part
+  );
+}
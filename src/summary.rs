@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use crate::solvers::{DayReport, Part, alloc::AllocStats};
+
+const HEADERS: [&str; 8] = [
+  "Day", "Title", "Parse", "Part 1", "Time", "Part 2", "Time", "Total",
+];
+/// Followed by a heap column when built with the count-alloc feature
+const TIMED_COLUMNS: [usize; 3] = [2, 4, 6];
+
+pub fn print_summary(reports: &[DayReport]) {
+  let with_alloc = reports.iter().any(|r| r.parse_alloc.is_some());
+  let mut rows: Vec<[String; 8]> = reports.iter().map(row).collect();
+  let total: Duration = reports.iter().map(|r| r.total_duration()).sum();
+  rows.push([
+    String::new(),
+    "Total".to_string(),
+    format!(
+      "{:?}",
+      reports.iter().map(|r| r.parse_duration).sum::<Duration>()
+    ),
+    String::new(),
+    String::new(),
+    String::new(),
+    String::new(),
+    format!("{:?}", total),
+  ]);
+
+  let mut headers: Vec<String> = HEADERS.map(String::from).into();
+  let mut rows: Vec<Vec<String>> = rows.into_iter().map(Vec::from).collect();
+  if with_alloc {
+    let mut heaps: Vec<[String; 3]> = reports.iter().map(heap_row).collect();
+    heaps.push(Default::default());
+    // Right to left, so the earlier column indices stay valid
+    for (i, &col) in TIMED_COLUMNS.iter().enumerate().rev() {
+      headers.insert(col + 1, "Peak heap".to_string());
+      for (row, heap) in rows.iter_mut().zip(&heaps) {
+        row.insert(col + 1, heap[i].clone());
+      }
+    }
+  }
+
+  let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+  for row in &rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.chars().count());
+    }
+  }
+
+  print_row(&headers, &widths);
+  print_separator(&widths);
+  for (i, row) in rows.iter().enumerate() {
+    if i == rows.len() - 1 {
+      print_separator(&widths);
+    }
+    print_row(row, &widths);
+  }
+}
+
+fn row(report: &DayReport) -> [String; 8] {
+  let mut row = [
+    report.day.to_string(),
+    report.title.to_string(),
+    format!("{:?}", report.parse_duration),
+    String::new(),
+    String::new(),
+    String::new(),
+    String::new(),
+    format!("{:?}", report.total_duration()),
+  ];
+  for part in &report.parts {
+    let col = match part.part {
+      Part::One => 3,
+      Part::Two => 5,
+    };
+    row[col] = part
+      .answer
+      .clone()
+      .unwrap_or_else(|| "timed out".to_string());
+    if !part.examples.iter().all(|e| e.passed()) {
+      row[col] += " (example failed)";
+    }
+    if let Some(verdict) = part.verdict.as_ref().filter(|v| !v.is_match()) {
+      row[col] += &format!(" ({})", verdict);
+    }
+    row[col + 1] = format!("{:?}", part.duration);
+  }
+  row
+}
+
+/// Peak heap of parsing and each part, next to their times.
+fn heap_row(report: &DayReport) -> [String; 3] {
+  let peak = |stats: Option<AllocStats>| stats.map(|s| s.format_peak()).unwrap_or_default();
+  let mut row = [peak(report.parse_alloc), String::new(), String::new()];
+  for part in &report.parts {
+    row[part.part.number() as usize] = peak(part.alloc);
+  }
+  row
+}
+
+fn print_row(row: &[String], widths: &[usize]) {
+  let cells: Vec<String> = row
+    .iter()
+    .zip(widths)
+    .enumerate()
+    .map(|(i, (cell, width))| {
+      // Text columns read better left-aligned, numbers and durations right-aligned
+      if i == 1 {
+        format!("{:<width$}", cell)
+      } else {
+        format!("{:>width$}", cell)
+      }
+    })
+    .collect();
+  println!("{}", cells.join(" | "));
+}
+
+fn print_separator(widths: &[usize]) {
+  let dashes: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+  println!("{}", dashes.join("-+-"));
+}
@@ -0,0 +1,259 @@
+use std::{
+  fmt,
+  ops::{Index, IndexMut},
+};
+
+use crate::solvers::{Line, ParseError};
+
+/// Column and row, `(x, y)`, with the origin in the top left corner.
+pub type Point = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [
+  (-1, -1),
+  (0, -1),
+  (1, -1),
+  (1, 0),
+  (1, 1),
+  (0, 1),
+  (-1, 1),
+  (-1, 0),
+];
+
+/// A rectangular map of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  pub fn new(width: usize, height: usize, fill: T) -> Self
+  where
+    T: Clone,
+  {
+    Self {
+      width,
+      height,
+      cells: vec![fill; width * height],
+    }
+  }
+
+  /// Parses a character map, one row per line. `cell` maps each character or rejects
+  /// it with a reason, and every row must be as wide as the first.
+  pub fn parse(
+    day: u8,
+    input: &str,
+    mut cell: impl FnMut(char) -> Result<T, &'static str>,
+  ) -> Result<Self, ParseError> {
+    let mut width = None;
+    let mut height = 0;
+    let mut cells = Vec::new();
+    for (y, text) in input.lines().enumerate() {
+      let line = Line::new(day, y, text);
+      let mut row_width = 0;
+      for (x, c) in text.chars().enumerate() {
+        if width.is_some_and(|width| x >= width) {
+          return Err(line.error_at(x + 1, "row is wider than the first"));
+        }
+        cells.push(cell(c).map_err(|reason| line.error_at(x + 1, reason))?);
+        row_width += 1;
+      }
+      if width.is_some_and(|width| row_width < width) {
+        return Err(line.missing("row is narrower than the first"));
+      }
+      width = Some(row_width);
+      height += 1;
+    }
+
+    Ok(Self {
+      width: width.unwrap_or(0),
+      height,
+      cells,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn get(&self, (x, y): Point) -> Option<&T> {
+    self
+      .contains((x, y))
+      .then(|| &self.cells[y * self.width + x])
+  }
+
+  pub fn get_mut(&mut self, (x, y): Point) -> Option<&mut T> {
+    if self.contains((x, y)) {
+      Some(&mut self.cells[y * self.width + x])
+    } else {
+      None
+    }
+  }
+
+  pub fn contains(&self, (x, y): Point) -> bool {
+    x < self.width && y < self.height
+  }
+
+  /// Up, right, down and left, skipping those outside the grid.
+  pub fn neighbors_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+    self.offset_all(point, &OFFSETS_4)
+  }
+
+  /// Like `neighbors_4`, including diagonals.
+  pub fn neighbors_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+    self.offset_all(point, &OFFSETS_8)
+  }
+
+  fn offset_all<'a>(
+    &'a self,
+    (x, y): Point,
+    offsets: &'static [(isize, isize)],
+  ) -> impl Iterator<Item = Point> + 'a {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+      let neighbor = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+      self.contains(neighbor).then_some(neighbor)
+    })
+  }
+
+  pub fn row(&self, y: usize) -> &[T] {
+    &self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    // chunks panics on 0, an empty grid has no cells to chunk anyway
+    self.cells.chunks(self.width.max(1))
+  }
+
+  pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+    assert!(x < self.width, "Column {} is outside the grid", x);
+    self.cells.iter().skip(x).step_by(self.width)
+  }
+
+  /// Every cell with its position, row by row.
+  pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+    self
+      .cells
+      .iter()
+      .enumerate()
+      .map(|(i, cell)| ((i % self.width, i / self.width), cell))
+  }
+
+  /// The first position holding `value`, row by row.
+  pub fn find(&self, value: &T) -> Option<Point>
+  where
+    T: PartialEq,
+  {
+    self.find_all(value).next()
+  }
+
+  pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+  where
+    T: PartialEq,
+  {
+    self
+      .iter()
+      .filter(move |(_, cell)| *cell == value)
+      .map(|(point, _)| point)
+  }
+
+  /// Turns the grid back into a character map, the inverse of `parse`.
+  pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+    let mut text = String::with_capacity((self.width + 1) * self.height);
+    for row in self.rows() {
+      text.extend(row.iter().map(&cell));
+      text.push('\n');
+    }
+    text
+  }
+}
+
+impl<T> Index<Point> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, point: Point) -> &T {
+    self
+      .get(point)
+      .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+  }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+  fn index_mut(&mut self, point: Point) -> &mut T {
+    self
+      .get_mut(point)
+      .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+  }
+}
+
+impl fmt::Display for Grid<char> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.render(|c| *c))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Grid;
+  use crate::solvers::ParseError;
+
+  const MAP: &str = "#.S\n..#\n";
+
+  fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(0, input, |c| match c {
+      '#' | '.' | 'S' => Ok(c),
+      _ => Err("expected '#', '.' or 'S'"),
+    })
+  }
+
+  #[test]
+  fn parses_and_renders() {
+    let grid = parse(MAP).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 1)], '#');
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.row(1), ['.', '.', '#']);
+    assert_eq!(grid.column(2).collect::<String>(), "S#");
+    assert_eq!(grid.find(&'S'), Some((2, 0)));
+    assert_eq!(grid.find_all(&'#').collect::<Vec<_>>(), [(0, 0), (2, 1)]);
+    assert_eq!(grid.to_string(), MAP);
+  }
+
+  #[test]
+  fn neighbors_stay_in_bounds() {
+    let grid = parse(MAP).unwrap();
+    assert_eq!(
+      grid.neighbors_4((0, 0)).collect::<Vec<_>>(),
+      [(1, 0), (0, 1)]
+    );
+    assert_eq!(
+      grid.neighbors_8((2, 1)).collect::<Vec<_>>(),
+      [(1, 0), (2, 0), (1, 1)]
+    );
+    assert_eq!(grid.neighbors_8((1, 0)).count(), 5);
+  }
+
+  #[test]
+  fn reports_ragged_rows_and_invalid_cells() {
+    let error = parse("#.\n#.#\n").unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Day 0, line 2, column 3: row is wider than the first (found \"#\")"
+    );
+    let error = parse("#.\n#\n").unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Day 0, line 2, column 2: row is narrower than the first (found \"\")"
+    );
+    let error = parse("#x\n").unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Day 0, line 1, column 2: expected '#', '.' or 'S' (found \"x\")"
+    );
+  }
+}
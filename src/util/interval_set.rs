@@ -0,0 +1,214 @@
+use std::ops::RangeInclusive;
+
+/// Integer-like values, where every value has a direct neighbor on each side.
+pub trait Discrete: Copy + Ord {
+  /// The value right after this one, None at the maximum.
+  fn next(self) -> Option<Self>;
+  /// The value right before this one, None at the minimum.
+  fn prev(self) -> Option<Self>;
+  /// Number of values in `start..=end`, which must not be empty. A u128 holds even the
+  /// full range of a 64-bit type.
+  fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+  ($($t:ty),*) => {
+    $(impl Discrete for $t {
+      fn next(self) -> Option<Self> {
+        self.checked_add(1)
+      }
+
+      fn prev(self) -> Option<Self> {
+        self.checked_sub(1)
+      }
+
+      fn count(start: Self, end: Self) -> u128 {
+        (end as i128 - start as i128) as u128 + 1
+      }
+    })*
+  };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of values stored as sorted, disjoint inclusive ranges. Overlapping and adjacent
+/// ranges are merged as they are inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+  ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+  fn default() -> Self {
+    Self { ranges: Vec::new() }
+  }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  /// Adds every value in `range`, merging it with the ranges it overlaps or touches.
+  /// Empty ranges are ignored.
+  pub fn insert(&mut self, range: RangeInclusive<T>) {
+    let (mut start, mut end) = range.into_inner();
+    if start > end {
+      return;
+    }
+
+    // The ranges from `first` up to `last` overlap or touch the new one
+    let first = self
+      .ranges
+      .partition_point(|r| start.prev().is_some_and(|before| *r.end() < before));
+    let last = self
+      .ranges
+      .partition_point(|r| end.next().is_none_or(|after| *r.start() <= after));
+    if first < last {
+      start = start.min(*self.ranges[first].start());
+      end = end.max(*self.ranges[last - 1].end());
+    }
+    self.ranges.splice(first..last, [start..=end]);
+  }
+
+  pub fn contains(&self, value: T) -> bool {
+    let i = self.ranges.partition_point(|r| *r.end() < value);
+    self.ranges.get(i).is_some_and(|r| *r.start() <= value)
+  }
+
+  /// Number of values in the set.
+  pub fn covered_len(&self) -> u128 {
+    self
+      .ranges
+      .iter()
+      .map(|r| T::count(*r.start(), *r.end()))
+      .sum()
+  }
+
+  /// The disjoint ranges, in ascending order.
+  pub fn iter(&self) -> impl Iterator<Item = &RangeInclusive<T>> {
+    self.ranges.iter()
+  }
+
+  pub fn union(&self, other: &Self) -> Self {
+    let mut union = self.clone();
+    for range in &other.ranges {
+      union.insert(range.clone());
+    }
+    union
+  }
+
+  pub fn intersection(&self, other: &Self) -> Self {
+    let mut intersection = Self::new();
+    let (mut i, mut j) = (0, 0);
+    while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+      let start = *a.start().max(b.start());
+      let end = *a.end().min(b.end());
+      if start <= end {
+        intersection.ranges.push(start..=end);
+      }
+      if a.end() < b.end() {
+        i += 1;
+      } else {
+        j += 1;
+      }
+    }
+    intersection
+  }
+
+  /// The values in this set that are not in `other`.
+  pub fn difference(&self, other: &Self) -> Self {
+    let mut difference = Self::new();
+    for range in &self.ranges {
+      let (start, end) = (*range.start(), *range.end());
+      let first = other.ranges.partition_point(|r| *r.end() < start);
+      // Start of the part of `range` not yet cut or kept
+      let mut rest = Some(start);
+      for cut in other.ranges[first..]
+        .iter()
+        .take_while(|r| *r.start() <= end)
+      {
+        let Some(rest_start) = rest else {
+          break;
+        };
+        if let Some(before) = cut.start().prev().filter(|&before| before >= rest_start) {
+          difference.ranges.push(rest_start..=before);
+        }
+        rest = cut.end().next().filter(|&after| after <= end);
+      }
+      if let Some(rest_start) = rest {
+        difference.ranges.push(rest_start..=end);
+      }
+    }
+    difference
+  }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+  fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+    let mut set = Self::new();
+    for range in ranges {
+      set.insert(range);
+    }
+    set
+  }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+  type Item = &'a RangeInclusive<T>;
+  type IntoIter = std::slice::Iter<'a, RangeInclusive<T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.ranges.iter()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::IntervalSet;
+
+  fn ranges(set: &IntervalSet<u64>) -> Vec<(u64, u64)> {
+    set.iter().map(|r| (*r.start(), *r.end())).collect()
+  }
+
+  #[test]
+  fn merges_overlapping_and_adjacent_ranges() {
+    let mut set: IntervalSet<u64> = [10..=14, 3..=5, 16..=20, 12..=18].into_iter().collect();
+    assert_eq!(ranges(&set), [(3, 5), (10, 20)]);
+    set.insert(6..=9);
+    assert_eq!(ranges(&set), [(3, 20)]);
+    set.insert(0..=0);
+    set.insert(u64::MAX..=u64::MAX);
+    #[allow(clippy::reversed_empty_ranges)]
+    set.insert(30..=25);
+    assert_eq!(ranges(&set), [(0, 0), (3, 20), (u64::MAX, u64::MAX)]);
+    assert_eq!(set.covered_len(), 20);
+
+    assert!(set.contains(0) && set.contains(3) && set.contains(20));
+    assert!(!set.contains(1) && !set.contains(21));
+  }
+
+  #[test]
+  fn counts_full_domain_ranges() {
+    let set: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+    assert_eq!(set.covered_len(), 1 << 64);
+    let set: IntervalSet<i64> = [i64::MIN..=-1, 0..=i64::MAX].into_iter().collect();
+    assert_eq!(set.iter().count(), 1);
+    assert_eq!(set.covered_len(), 1 << 64);
+  }
+
+  #[test]
+  fn combines_sets() {
+    let a: IntervalSet<u64> = [1..=5, 10..=15].into_iter().collect();
+    let b: IntervalSet<u64> = [4..=11, 15..=20].into_iter().collect();
+    assert_eq!(ranges(&a.union(&b)), [(1, 20)]);
+    assert_eq!(ranges(&a.intersection(&b)), [(4, 5), (10, 11), (15, 15)]);
+    assert_eq!(ranges(&a.difference(&b)), [(1, 3), (12, 14)]);
+    assert_eq!(ranges(&b.difference(&a)), [(6, 9), (16, 20)]);
+    assert!(a.difference(&a).is_empty());
+  }
+}
@@ -0,0 +1,11 @@
+//! Data structures and algorithms shared between days.
+
+pub mod grid;
+pub mod interval_set;
+pub mod search;
+pub mod union_find;
+
+pub use grid::{Grid, Point};
+pub use interval_set::IntervalSet;
+pub use search::{SearchSpace, Solution, Stats};
+pub use union_find::UnionFind;
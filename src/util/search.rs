@@ -0,0 +1,431 @@
+use std::{
+  cmp::Ordering,
+  collections::{BinaryHeap, VecDeque, hash_map::Entry},
+  hash::Hash,
+  ops::Add,
+};
+
+use rustc_hash::FxHashMap;
+
+/// A graph to search, defined by its successor function.
+pub trait SearchSpace {
+  type State: Clone + Eq + Hash;
+  /// `Default` is the zero cost
+  type Cost: Copy + Ord + Default + Add<Output = Self::Cost>;
+
+  /// States reachable in one step, with the cost of the step.
+  fn successors(&self, state: &Self::State) -> impl Iterator<Item = (Self::State, Self::Cost)>;
+
+  fn is_goal(&self, state: &Self::State) -> bool;
+
+  /// Estimate of the remaining cost to a goal, used by `astar`. It must never
+  /// overestimate, or the path found may not be the cheapest.
+  fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+    Self::Cost::default()
+  }
+
+  /// Called for every state taken off the frontier, before its goal test.
+  fn on_expand(&self, _state: &Self::State, _stats: &Stats) {}
+
+  /// Checked before every expansion, the search gives up and returns None once it is set.
+  fn is_cancelled(&self) -> bool {
+    false
+  }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+  /// States taken off the frontier
+  pub expanded: usize,
+  /// States put on the frontier, including the start
+  pub generated: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution<S, C> {
+  pub cost: C,
+  /// From the start to the goal, both included
+  pub path: Vec<S>,
+  pub stats: Stats,
+}
+
+/// What a search remembers about the states it reached.
+trait Explored<S, C>: Default {
+  /// Handle to a reached state, carried on the frontier
+  type Node;
+  type Output;
+
+  /// Records reaching `state`, None if it was already reached as cheaply.
+  fn reach(&mut self, state: S, cost: C, parent: Option<&Self::Node>) -> Option<Self::Node>;
+  fn contains(&self, state: &S) -> bool;
+  /// False if the state was reached more cheaply after `node` was, at `cost`.
+  fn is_current(&self, node: &Self::Node, cost: C) -> bool;
+  fn state<'a>(&'a self, node: &'a Self::Node) -> &'a S;
+  fn finish(self, node: Self::Node, cost: C, stats: Stats) -> Self::Output;
+}
+
+/// Only the cheapest cost of each state.
+struct Costs<S, C>(FxHashMap<S, C>);
+
+impl<S, C> Default for Costs<S, C> {
+  fn default() -> Self {
+    Self(FxHashMap::default())
+  }
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> Explored<S, C> for Costs<S, C> {
+  type Node = S;
+  type Output = C;
+
+  fn reach(&mut self, state: S, cost: C, _parent: Option<&S>) -> Option<S> {
+    match self.0.entry(state.clone()) {
+      Entry::Occupied(entry) if *entry.get() <= cost => return None,
+      Entry::Occupied(mut entry) => {
+        entry.insert(cost);
+      }
+      Entry::Vacant(entry) => {
+        entry.insert(cost);
+      }
+    }
+    Some(state)
+  }
+
+  fn contains(&self, state: &S) -> bool {
+    self.0.contains_key(state)
+  }
+
+  fn is_current(&self, state: &S, cost: C) -> bool {
+    self.0[state] == cost
+  }
+
+  fn state<'a>(&'a self, state: &'a S) -> &'a S {
+    state
+  }
+
+  fn finish(self, _node: S, cost: C, _stats: Stats) -> C {
+    cost
+  }
+}
+
+/// Reached states, each pointing back at the state it was reached from.
+struct Tree<S, C> {
+  nodes: Vec<(S, Option<usize>)>,
+  /// Cheapest known cost and node of each state
+  best: FxHashMap<S, (C, usize)>,
+}
+
+impl<S, C> Default for Tree<S, C> {
+  fn default() -> Self {
+    Self {
+      nodes: Vec::new(),
+      best: FxHashMap::default(),
+    }
+  }
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> Explored<S, C> for Tree<S, C> {
+  type Node = usize;
+  type Output = Solution<S, C>;
+
+  fn reach(&mut self, state: S, cost: C, parent: Option<&usize>) -> Option<usize> {
+    let index = self.nodes.len();
+    match self.best.entry(state.clone()) {
+      Entry::Occupied(entry) if entry.get().0 <= cost => return None,
+      Entry::Occupied(mut entry) => {
+        entry.insert((cost, index));
+      }
+      Entry::Vacant(entry) => {
+        entry.insert((cost, index));
+      }
+    }
+    self.nodes.push((state, parent.copied()));
+    Some(index)
+  }
+
+  fn contains(&self, state: &S) -> bool {
+    self.best.contains_key(state)
+  }
+
+  fn is_current(&self, &index: &usize, _cost: C) -> bool {
+    self.best[&self.nodes[index].0].1 == index
+  }
+
+  fn state<'a>(&'a self, &index: &'a usize) -> &'a S {
+    &self.nodes[index].0
+  }
+
+  fn finish(self, mut index: usize, cost: C, stats: Stats) -> Solution<S, C> {
+    let mut path = Vec::new();
+    loop {
+      let (state, parent) = &self.nodes[index];
+      path.push(state.clone());
+      match parent {
+        Some(parent) => index = *parent,
+        None => break,
+      }
+    }
+    path.reverse();
+    Solution { cost, path, stats }
+  }
+}
+
+/// Breadth-first search, finds the path with the fewest steps. Its cost is the sum of
+/// the step costs along it, which is only the cheapest if all steps cost the same.
+pub fn bfs<G: SearchSpace>(space: &G, start: G::State) -> Option<Solution<G::State, G::Cost>> {
+  breadth_first::<G, Tree<_, _>>(space, start)
+}
+
+/// Like `bfs`, but only returns the cost. Needs less memory, as it does not keep what
+/// it takes to rebuild the path.
+pub fn bfs_cost<G: SearchSpace>(space: &G, start: G::State) -> Option<G::Cost> {
+  breadth_first::<G, Costs<_, _>>(space, start)
+}
+
+/// Finds the cheapest path, without a heuristic.
+pub fn dijkstra<G: SearchSpace>(space: &G, start: G::State) -> Option<Solution<G::State, G::Cost>> {
+  best_first::<G, Tree<_, _>>(space, start, false)
+}
+
+/// Like `dijkstra`, but only returns the cost.
+pub fn dijkstra_cost<G: SearchSpace>(space: &G, start: G::State) -> Option<G::Cost> {
+  best_first::<G, Costs<_, _>>(space, start, false)
+}
+
+/// Finds the cheapest path, guided by `SearchSpace::heuristic`.
+pub fn astar<G: SearchSpace>(space: &G, start: G::State) -> Option<Solution<G::State, G::Cost>> {
+  best_first::<G, Tree<_, _>>(space, start, true)
+}
+
+/// Like `astar`, but only returns the cost.
+pub fn astar_cost<G: SearchSpace>(space: &G, start: G::State) -> Option<G::Cost> {
+  best_first::<G, Costs<_, _>>(space, start, true)
+}
+
+fn breadth_first<G: SearchSpace, E: Explored<G::State, G::Cost>>(
+  space: &G,
+  start: G::State,
+) -> Option<E::Output> {
+  let mut explored = E::default();
+  let mut stats = Stats::default();
+  let mut frontier = VecDeque::new();
+  frontier.push_back((
+    explored.reach(start, G::Cost::default(), None)?,
+    G::Cost::default(),
+  ));
+  stats.generated += 1;
+
+  while let Some((node, cost)) = frontier.pop_front() {
+    if space.is_cancelled() {
+      return None;
+    }
+    stats.expanded += 1;
+    let state = explored.state(&node).clone();
+    space.on_expand(&state, &stats);
+    if space.is_goal(&state) {
+      return Some(explored.finish(node, cost, stats));
+    }
+    for (next, step) in space.successors(&state) {
+      // Any earlier visit took no more steps, so only unseen states matter
+      if !explored.contains(&next) {
+        let next_cost = cost + step;
+        if let Some(next_node) = explored.reach(next, next_cost, Some(&node)) {
+          frontier.push_back((next_node, next_cost));
+          stats.generated += 1;
+        }
+      }
+    }
+  }
+  None
+}
+
+/// Frontier entry ordered by estimate alone, so nodes need no ordering of their own.
+struct Queued<N, C> {
+  estimate: C,
+  cost: C,
+  node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+  /// Cheapest estimate first, ties go to the deepest state
+  fn cmp(&self, other: &Self) -> Ordering {
+    other
+      .estimate
+      .cmp(&self.estimate)
+      .then_with(|| self.cost.cmp(&other.cost))
+  }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+fn best_first<G: SearchSpace, E: Explored<G::State, G::Cost>>(
+  space: &G,
+  start: G::State,
+  use_heuristic: bool,
+) -> Option<E::Output> {
+  let estimate = |state: &G::State, cost: G::Cost| {
+    if use_heuristic {
+      cost + space.heuristic(state)
+    } else {
+      cost
+    }
+  };
+
+  let mut explored = E::default();
+  let mut stats = Stats::default();
+  let mut frontier = BinaryHeap::new();
+  let start_estimate = estimate(&start, G::Cost::default());
+  frontier.push(Queued {
+    estimate: start_estimate,
+    cost: G::Cost::default(),
+    node: explored.reach(start, G::Cost::default(), None)?,
+  });
+  stats.generated += 1;
+
+  while let Some(Queued { cost, node, .. }) = frontier.pop() {
+    // A cheaper way to this state was found after this entry was pushed
+    if !explored.is_current(&node, cost) {
+      continue;
+    }
+    if space.is_cancelled() {
+      return None;
+    }
+    stats.expanded += 1;
+    let state = explored.state(&node).clone();
+    space.on_expand(&state, &stats);
+    if space.is_goal(&state) {
+      return Some(explored.finish(node, cost, stats));
+    }
+    for (next, step) in space.successors(&state) {
+      let next_cost = cost + step;
+      if let Some(next_node) = explored.reach(next, next_cost, Some(&node)) {
+        frontier.push(Queued {
+          estimate: estimate(explored.state(&next_node), next_cost),
+          cost: next_cost,
+          node: next_node,
+        });
+        stats.generated += 1;
+      }
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{SearchSpace, Stats, astar, astar_cost, bfs, bfs_cost, dijkstra, dijkstra_cost};
+  use crate::util::{Grid, Point};
+
+  /// Digits are the cost of entering a cell, `#` is a wall.
+  struct Maze(Grid<Option<u32>>);
+
+  impl Maze {
+    fn new(map: &str) -> Self {
+      Self(Grid::parse(0, map, |c| Ok(c.to_digit(10))).unwrap())
+    }
+
+    fn goal(&self) -> Point {
+      (self.0.width() - 1, self.0.height() - 1)
+    }
+  }
+
+  impl SearchSpace for Maze {
+    type State = Point;
+    type Cost = u32;
+
+    fn successors(&self, &point: &Point) -> impl Iterator<Item = (Point, u32)> {
+      self
+        .0
+        .neighbors_4(point)
+        .filter_map(|next| Some((next, self.0[next]?)))
+    }
+
+    fn is_goal(&self, point: &Point) -> bool {
+      *point == self.goal()
+    }
+
+    fn heuristic(&self, &(x, y): &Point) -> u32 {
+      let (goal_x, goal_y) = self.goal();
+      (goal_x.abs_diff(x) + goal_y.abs_diff(y)) as u32
+    }
+  }
+
+  const MAP: &str = "\
+1911
+1#91
+1111
+";
+
+  #[test]
+  fn bfs_takes_the_fewest_steps() {
+    let solution = bfs(&Maze::new(MAP), (0, 0)).unwrap();
+    assert_eq!(solution.path.len(), 6);
+    assert_eq!(solution.path.first(), Some(&(0, 0)));
+    assert_eq!(solution.path.last(), Some(&(3, 2)));
+  }
+
+  #[test]
+  fn dijkstra_and_astar_find_the_cheapest_path() {
+    let maze = Maze::new(MAP);
+    let cheapest = vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (3, 2)];
+    let dijkstra = dijkstra(&maze, (0, 0)).unwrap();
+    let astar = astar(&maze, (0, 0)).unwrap();
+    assert_eq!((dijkstra.cost, &dijkstra.path), (5, &cheapest));
+    assert_eq!((astar.cost, &astar.path), (5, &cheapest));
+    assert!(astar.stats.expanded <= dijkstra.stats.expanded);
+  }
+
+  #[test]
+  fn cost_only_searches_agree() {
+    let maze = Maze::new(MAP);
+    assert_eq!(dijkstra_cost(&maze, (0, 0)), Some(5));
+    assert_eq!(astar_cost(&maze, (0, 0)), Some(5));
+    assert_eq!(bfs_cost(&maze, (0, 0)), bfs(&maze, (0, 0)).map(|s| s.cost));
+    assert_eq!(astar_cost(&Maze::new("1#\n#1\n"), (0, 0)), None);
+  }
+
+  #[test]
+  fn reports_unreachable_goals() {
+    let maze = Maze::new("1#\n#1\n");
+    assert_eq!(dijkstra(&maze, (0, 0)), None);
+    assert_eq!(bfs(&maze, (0, 0)), None);
+  }
+
+  #[test]
+  fn calls_the_expand_hook() {
+    struct Counting(std::cell::Cell<usize>);
+
+    impl SearchSpace for Counting {
+      type State = u32;
+      type Cost = u32;
+
+      fn successors(&self, &n: &u32) -> impl Iterator<Item = (u32, u32)> {
+        [(n + 1, 1), (n * 2, 1)].into_iter()
+      }
+
+      fn is_goal(&self, &n: &u32) -> bool {
+        n == 10
+      }
+
+      fn on_expand(&self, _state: &u32, stats: &Stats) {
+        self.0.set(stats.expanded);
+      }
+    }
+
+    let space = Counting(std::cell::Cell::new(0));
+    let solution = bfs(&space, 1).unwrap();
+    assert_eq!(solution.path, [1, 2, 4, 5, 10]);
+    assert_eq!(space.0.get(), solution.stats.expanded);
+  }
+}
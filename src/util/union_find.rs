@@ -0,0 +1,166 @@
+/// Disjoint sets over the elements `0..len`, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+  parents: Vec<usize>,
+  /// Only meaningful for roots
+  sizes: Vec<usize>,
+  components: usize,
+}
+
+impl UnionFind {
+  /// Every element starts out in a set of its own.
+  pub fn new(len: usize) -> Self {
+    Self {
+      parents: (0..len).collect(),
+      sizes: vec![1; len],
+      components: len,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.parents.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.parents.is_empty()
+  }
+
+  /// The representative of the set holding `x`.
+  pub fn find(&mut self, x: usize) -> usize {
+    let mut root = x;
+    while self.parents[root] != root {
+      root = self.parents[root];
+    }
+
+    // Point everything on the way straight at the root
+    let mut x = x;
+    while self.parents[x] != root {
+      x = std::mem::replace(&mut self.parents[x], root);
+    }
+    root
+  }
+
+  /// Merges the sets holding `a` and `b`, returns false if they already were one.
+  pub fn union(&mut self, a: usize, b: usize) -> bool {
+    let (mut root_a, mut root_b) = (self.find(a), self.find(b));
+    if root_a == root_b {
+      return false;
+    }
+    if self.sizes[root_a] < self.sizes[root_b] {
+      std::mem::swap(&mut root_a, &mut root_b);
+    }
+    self.parents[root_b] = root_a;
+    self.sizes[root_a] += self.sizes[root_b];
+    self.components -= 1;
+    true
+  }
+
+  pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+    self.find(a) == self.find(b)
+  }
+
+  /// Size of the set holding `x`.
+  pub fn size(&mut self, x: usize) -> usize {
+    let root = self.find(x);
+    self.sizes[root]
+  }
+
+  /// Number of disjoint sets.
+  pub fn components(&self) -> usize {
+    self.components
+  }
+
+  /// The elements of each set, in ascending order. Sets are ordered by their smallest element.
+  pub fn groups(&mut self) -> impl Iterator<Item = Vec<usize>> {
+    let mut group_of_root = vec![usize::MAX; self.len()];
+    let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+    for x in 0..self.len() {
+      let root = self.find(x);
+      if group_of_root[root] == usize::MAX {
+        group_of_root[root] = groups.len();
+        groups.push(Vec::with_capacity(self.sizes[root]));
+      }
+      groups[group_of_root[root]].push(x);
+    }
+    groups.into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::UnionFind;
+
+  #[test]
+  fn tracks_sets_and_sizes() {
+    let mut sets = UnionFind::new(6);
+    assert_eq!(sets.components(), 6);
+    assert!(sets.union(0, 1));
+    assert!(sets.union(2, 3));
+    assert!(sets.union(1, 3));
+    assert!(!sets.union(0, 2));
+
+    assert!(sets.same_set(0, 3));
+    assert!(!sets.same_set(0, 4));
+    assert_eq!(sets.size(2), 4);
+    assert_eq!(sets.size(5), 1);
+    assert_eq!(sets.components(), 3);
+    assert_eq!(
+      sets.groups().collect::<Vec<_>>(),
+      [vec![0, 1, 2, 3], vec![4], vec![5]]
+    );
+  }
+
+  #[test]
+  fn merges_long_chains() {
+    let mut sets = UnionFind::new(100_000);
+    for x in 1..sets.len() {
+      sets.union(x - 1, x);
+    }
+    assert_eq!(sets.components(), 1);
+    assert_eq!(sets.size(99_999), 100_000);
+  }
+
+  #[test]
+  fn find_compresses_paths() {
+    // Union by size never builds a chain, so build one by hand: 0 <- 1 <- ... <- 999
+    let mut sets = UnionFind::new(1000);
+    for x in 1..sets.len() {
+      sets.parents[x] = x - 1;
+    }
+    sets.sizes[0] = sets.len();
+    sets.components = 1;
+
+    assert_eq!(sets.find(999), 0);
+    assert!(sets.parents.iter().all(|&parent| parent == 0));
+    assert_eq!(sets.groups().count(), 1);
+  }
+
+  #[test]
+  fn flattens_trees_from_equal_merges() {
+    // Merging equal sizes grows the tree by a level each round
+    let mut sets = UnionFind::new(1 << 10);
+    let mut width = 1;
+    while width < sets.len() {
+      for x in (0..sets.len()).step_by(2 * width) {
+        // Roots, so the merge itself does not compress anything
+        let (a, b) = (sets.parents[x], sets.parents[x + width]);
+        sets.union(a, b);
+      }
+      width *= 2;
+    }
+    let depth = |sets: &UnionFind, mut x: usize| {
+      let mut depth = 0;
+      while sets.parents[x] != x {
+        x = sets.parents[x];
+        depth += 1;
+      }
+      depth
+    };
+    let deepest = (0..sets.len()).max_by_key(|&x| depth(&sets, x)).unwrap();
+    assert_eq!(depth(&sets, deepest), 10);
+
+    let root = sets.find(deepest);
+    assert_eq!(depth(&sets, deepest), 1);
+    assert_eq!(sets.size(root), 1 << 10);
+  }
+}
@@ -0,0 +1,129 @@
+use std::{
+  fs,
+  panic::{self, AssertUnwindSafe},
+  thread,
+  time::{Duration, SystemTime},
+};
+
+use crate::solvers::{
+  DayReport, DynSolver, Ledger, RunOptions,
+  runner::{self, PartReport},
+  style,
+};
+
+type Snapshot = Vec<(String, Option<SystemTime>, u64)>;
+
+/// Re-runs the day whenever a file in its input directory changes. Only returns on errors.
+pub fn watch(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  options: &RunOptions,
+  interval: Duration,
+) -> Result<bool, String> {
+  println!(
+    "Watching {} for day {}: {}, press Ctrl-C to stop",
+    day_path,
+    solver.day(),
+    solver.title()
+  );
+
+  let mut snapshot = None;
+  let mut previous: Option<Vec<String>> = None;
+  loop {
+    let current = snapshot_dir(day_path)?;
+    if snapshot.as_ref() != Some(&current) {
+      if snapshot.is_some() {
+        println!();
+        println!("Change detected in {}, re-running", day_path);
+      }
+      snapshot = Some(current);
+      if let Some(answers) = run_once(solver, day_path, options, previous.as_deref()) {
+        previous = Some(answers);
+      }
+    }
+    thread::sleep(interval);
+  }
+}
+
+/// Names, modification times and sizes of the files in the directory.
+fn snapshot_dir(path: &str) -> Result<Snapshot, String> {
+  let entries = fs::read_dir(path).map_err(|e| format!("Could not watch {}: {}", path, e))?;
+  let mut snapshot: Snapshot = entries
+    .flatten()
+    .filter_map(|entry| {
+      let metadata = entry.metadata().ok()?;
+      metadata.is_file().then(|| {
+        (
+          entry.file_name().to_string_lossy().into_owned(),
+          metadata.modified().ok(),
+          metadata.len(),
+        )
+      })
+    })
+    .collect();
+  snapshot.sort();
+  Ok(snapshot)
+}
+
+/// Returns the answers, or None if the input could not be parsed or the solver panicked.
+fn run_once(
+  solver: &dyn DynSolver,
+  day_path: &str,
+  options: &RunOptions,
+  previous: Option<&[String]>,
+) -> Option<Vec<String>> {
+  let mut ledger = Ledger::load(&Ledger::path(day_path))
+    .map_err(|error| eprintln!("{}", error))
+    .ok();
+  // A half-written solver may well hit a todo!(), which should not end the watch
+  let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    runner::measure(solver, day_path, options, ledger.as_mut())
+  }));
+  let report = match result {
+    Ok(Ok(report)) => report,
+    Ok(Err(error)) => {
+      println!("{}", error);
+      return None;
+    }
+    Err(_) => {
+      println!("Day {} panicked, waiting for changes", solver.day());
+      return None;
+    }
+  };
+
+  print_report(&report, previous);
+  Some(report.parts.iter().map(answer_text).collect())
+}
+
+fn answer_text(part: &PartReport) -> String {
+  part
+    .answer
+    .clone()
+    .unwrap_or_else(|| "timed out".to_string())
+}
+
+fn print_report(report: &DayReport, previous: Option<&[String]>) {
+  for example in report.parts.iter().flat_map(|p| &p.examples) {
+    example.print();
+  }
+  println!("Parsing took {:?}", report.parse_duration);
+  for (i, part) in report.parts.iter().enumerate() {
+    let answer = answer_text(part);
+    let change = match previous.map(|answers| &answers[i]) {
+      Some(previous) if *previous == answer => " (unchanged)".to_string(),
+      Some(previous) => format!(" (was {})", previous),
+      None => String::new(),
+    };
+    let verdict = match &part.verdict {
+      Some(verdict) if verdict.is_match() => {
+        format!(" {}", style::green(&format!("({})", verdict)))
+      }
+      Some(verdict) => format!(" {}", style::red(&format!("({})", verdict))),
+      None => String::new(),
+    };
+    println!(
+      "Part {}: {}{}{} in {:?}",
+      part.part, answer, change, verdict, part.duration
+    );
+  }
+}
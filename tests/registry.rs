@@ -0,0 +1,25 @@
+use advent_of_code_2025::solvers::{self, Params, Part, Progress, Solver, day3::Day3};
+
+#[test]
+fn registry_lists_each_day_once_in_order() {
+  let days: Vec<u8> = solvers::registry().iter().map(|s| s.day()).collect();
+  assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", days);
+  assert!(solvers::find(days[0]).is_some());
+  assert!(solvers::find(0).is_none());
+}
+
+#[test]
+fn solves_through_the_public_api() {
+  let banks = Day3::parse_str("987654321111111\n811111111111119\n").unwrap();
+  assert_eq!(Day3::part_1(&banks), 98 + 89);
+
+  let solver = solvers::find(3).unwrap();
+  let parsed_input = solver.parse("987654321111111\n").unwrap();
+  let answer = solver.solve(
+    Part::One,
+    &parsed_input,
+    &Params::real(solver.params()),
+    &Progress::disabled(),
+  );
+  assert_eq!(answer, "98");
+}